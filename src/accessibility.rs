@@ -0,0 +1,227 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fruits::FruitType,
+    settings::{FruitSlot, GameSettings},
+    storage,
+    ui::{ColorRole, FillRole, TextRole, Theme},
+};
+
+const ACCESSIBILITY_SAVE: &str = "accessibility";
+// Text sizes the options screen steps through
+pub const TEXT_SCALES: [f32; 3] = [1.0, 1.15, 1.3];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessibilityToggle {
+    FruitMarks,
+    LargeFruit,
+}
+
+impl AccessibilityToggle {
+    pub const ALL: [AccessibilityToggle; 2] = [
+        AccessibilityToggle::FruitMarks,
+        AccessibilityToggle::LargeFruit,
+    ];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            AccessibilityToggle::FruitMarks => "accessibility.fruit_marks",
+            AccessibilityToggle::LargeFruit => "accessibility.large_fruit",
+        }
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    // Letter and shape badges telling Fruit A and Fruit B apart without colour
+    pub fruit_marks: bool,
+    // Fewer, bigger cells in the counting grid
+    pub large_fruit: bool,
+    pub text_scale: f32,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            fruit_marks: false,
+            large_fruit: false,
+            text_scale: 1.0,
+        }
+    }
+}
+
+impl AccessibilitySettings {
+    pub fn enabled(&self, toggle: AccessibilityToggle) -> bool {
+        match toggle {
+            AccessibilityToggle::FruitMarks => self.fruit_marks,
+            AccessibilityToggle::LargeFruit => self.large_fruit,
+        }
+    }
+
+    pub fn flip(&mut self, toggle: AccessibilityToggle) {
+        match toggle {
+            AccessibilityToggle::FruitMarks => self.fruit_marks = !self.fruit_marks,
+            AccessibilityToggle::LargeFruit => self.large_fruit = !self.large_fruit,
+        }
+    }
+
+    pub fn next_text_scale(&mut self) {
+        let index = TEXT_SCALES
+            .iter()
+            .position(|scale| *scale == self.text_scale)
+            .unwrap_or(0);
+        self.text_scale = TEXT_SCALES[(index + 1) % TEXT_SCALES.len()];
+    }
+
+    pub fn save(&self) {
+        storage::save(ACCESSIBILITY_SAVE, self);
+    }
+}
+
+/// Badge in the corner of a fruit icon, a square A or a diamond B
+#[derive(Component, Clone, Copy, PartialEq)]
+pub struct FruitMark(pub Option<FruitSlot>);
+
+#[derive(Component)]
+pub struct MarkShape;
+
+#[derive(Component)]
+pub struct MarkLetter;
+
+/// Spawns a hidden mark, `draw_fruit_marks` shows it once the slot and setting allow
+pub fn spawn_fruit_mark<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &Theme,
+    slot: Option<FruitSlot>,
+) -> EntityCommands<'w, 's, 'a> {
+    let mut mark = parent.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(-6.0),
+                right: Val::Px(-6.0),
+                width: Val::Px(22.0),
+                height: Val::Px(22.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        FruitMark(slot),
+    ));
+    mark.with_children(|parent| {
+        parent.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+                background_color: theme.text.into(),
+                ..default()
+            },
+            MarkShape,
+            FillRole(ColorRole::Text),
+        ));
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 18.0,
+                    color: theme.button,
+                },
+            ),
+            MarkLetter,
+            TextRole(ColorRole::Button),
+        ));
+    });
+    mark
+}
+
+pub fn load_accessibility_settings(mut accessibility: ResMut<AccessibilitySettings>) {
+    if let Some(saved) = storage::load::<AccessibilitySettings>(ACCESSIBILITY_SAVE) {
+        *accessibility = saved;
+    }
+}
+
+// Marks on the fruit picker follow whichever slot the fruit is assigned to
+pub fn assign_fruit_marks(
+    game_settings: Res<GameSettings>,
+    mut mark_query: Query<(&FruitType, &mut FruitMark)>,
+) {
+    for (fruit_type, mut mark) in mark_query.iter_mut() {
+        if !game_settings.is_changed() && !mark.is_added() {
+            continue;
+        }
+        let slot = if *fruit_type == game_settings.fruit_a {
+            Some(FruitSlot::A)
+        } else if *fruit_type == game_settings.fruit_b {
+            Some(FruitSlot::B)
+        } else {
+            None
+        };
+        if mark.0 != slot {
+            mark.0 = slot;
+        }
+    }
+}
+
+pub fn draw_fruit_marks(
+    accessibility: Res<AccessibilitySettings>,
+    mut mark_query: Query<(Ref<FruitMark>, &mut Visibility, &Children)>,
+    mut shape_query: Query<&mut Transform, With<MarkShape>>,
+    mut letter_query: Query<&mut Text, With<MarkLetter>>,
+) {
+    for (mark, mut visibility, children) in mark_query.iter_mut() {
+        if !mark.is_changed() && !accessibility.is_changed() {
+            continue;
+        }
+        // Inherited rather than Visible so marks still hide with their fruit
+        *visibility = match mark.0 {
+            Some(_) if accessibility.fruit_marks => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+        let (letter, rotation) = match mark.0 {
+            Some(FruitSlot::B) => ("B", 45_f32.to_radians()),
+            _ => ("A", 0.0),
+        };
+        for child in children.iter() {
+            if let Ok(mut transform) = shape_query.get_mut(*child) {
+                transform.rotation = Quat::from_rotation_z(rotation);
+            }
+            if let Ok(mut text) = letter_query.get_mut(*child) {
+                text.sections[0].value = letter.to_string();
+            }
+        }
+    }
+}
+
+// Every screen spawns text at its base size, new text is scaled as it appears
+// and existing text is rescaled when the setting changes
+pub fn scale_text(
+    accessibility: Res<AccessibilitySettings>,
+    mut applied: Local<Option<f32>>,
+    mut text_query: Query<&mut Text>,
+) {
+    let scale = accessibility.text_scale;
+    let rescale = scale / applied.unwrap_or(1.0);
+    for mut text in text_query.iter_mut() {
+        let factor = if text.is_added() {
+            scale
+        } else if rescale != 1.0 {
+            rescale
+        } else {
+            continue;
+        };
+        for section in text.sections.iter_mut() {
+            section.style.font_size *= factor;
+        }
+    }
+    *applied = Some(scale);
+}
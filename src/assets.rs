@@ -0,0 +1,150 @@
+use bevy::{asset::UntypedAssetId, prelude::*, utils::HashMap};
+
+use crate::{
+    audio::{SfxKind, MUSIC_TRACKS},
+    fruits::FruitCatalogue,
+    locale::{locale_path, LocaleFile, LANGUAGES},
+    settings::Villagers,
+    village,
+};
+
+pub const FONT: &str = "fonts/Leila-Regular.ttf";
+// Fonts a locale can switch to for glyphs Leila doesn't have
+pub const FALLBACK_FONTS: [&str; 1] = ["fonts/DejaVuSans.ttf"];
+
+/// Every asset the game loads at runtime, resolved up front so the loading
+/// screen can report anything missing before it is needed
+#[derive(Resource, Default)]
+pub struct GameAssets {
+    pub font: Handle<Font>,
+    pub fallback_fonts: HashMap<&'static str, Handle<Font>>,
+    // Keyed by language code
+    pub locales: HashMap<&'static str, Handle<LocaleFile>>,
+    pub title: Handle<Image>,
+    pub background: Handle<Image>,
+    pub loading: Handle<Image>,
+    // Keyed by asset path, full and solo sprites and the walk sheet of every villager
+    pub characters: HashMap<String, Handle<Image>>,
+    // Keyed by asset path, filled in once the fruit catalogue has loaded
+    pub fruit_icons: HashMap<String, Handle<Image>>,
+    pub music: HashMap<&'static str, Handle<AudioSource>>,
+    pub sfx: HashMap<SfxKind, Handle<AudioSource>>,
+}
+
+impl GameAssets {
+    pub fn font(&self, path: &str) -> Handle<Font> {
+        if path == FONT {
+            return self.font.clone();
+        }
+        lookup(&self.fallback_fonts, path)
+    }
+
+    pub fn character(&self, path: &str) -> Handle<Image> {
+        lookup(&self.characters, path)
+    }
+
+    pub fn fruit_icon(&self, path: &str) -> Handle<Image> {
+        lookup(&self.fruit_icons, path)
+    }
+
+    pub fn music(&self, path: &str) -> Handle<AudioSource> {
+        lookup(&self.music, path)
+    }
+
+    pub fn sfx(&self, kind: SfxKind) -> Handle<AudioSource> {
+        self.sfx.get(&kind).cloned().unwrap_or_default()
+    }
+
+    /// Every asset the loading screen waits on
+    pub fn untyped_ids(&self) -> Vec<UntypedAssetId> {
+        let mut ids = vec![
+            self.font.id().untyped(),
+            self.title.id().untyped(),
+            self.background.id().untyped(),
+            self.loading.id().untyped(),
+        ];
+        ids.extend(
+            self.fallback_fonts
+                .values()
+                .map(|handle| handle.id().untyped()),
+        );
+        ids.extend(self.locales.values().map(|handle| handle.id().untyped()));
+        ids.extend(self.characters.values().map(|handle| handle.id().untyped()));
+        ids.extend(
+            self.fruit_icons
+                .values()
+                .map(|handle| handle.id().untyped()),
+        );
+        ids.extend(self.music.values().map(|handle| handle.id().untyped()));
+        ids.extend(self.sfx.values().map(|handle| handle.id().untyped()));
+        ids
+    }
+}
+
+// Paths outside the manifest are a bug, they show up as a blank image rather than a panic
+fn lookup<K, A>(handles: &HashMap<K, Handle<A>>, path: &str) -> Handle<A>
+where
+    K: std::borrow::Borrow<str> + std::hash::Hash + Eq,
+    A: Asset,
+{
+    match handles.get(path) {
+        Some(handle) => handle.clone(),
+        None => {
+            warn!("Asset {} is not in the manifest", path);
+            Handle::default()
+        }
+    }
+}
+
+pub fn load_assets(
+    asset_server: Res<AssetServer>,
+    villagers: Res<Villagers>,
+    mut assets: ResMut<GameAssets>,
+) {
+    assets.font = asset_server.load(FONT);
+    for path in FALLBACK_FONTS {
+        assets.fallback_fonts.insert(path, asset_server.load(path));
+    }
+    for code in LANGUAGES {
+        assets
+            .locales
+            .insert(code, asset_server.load(locale_path(code)));
+    }
+    assets.title = asset_server.load("icons/Title.png");
+    assets.background = asset_server.load("background/background.png");
+    assets.loading = asset_server.load("icons/loading.png");
+    for villager in villagers.villagers.iter() {
+        for path in [
+            villager.0.clone(),
+            villager.1.clone(),
+            village::walk_sheet_path(&villager.1),
+        ] {
+            assets
+                .characters
+                .insert(path.clone(), asset_server.load(path));
+        }
+    }
+    for path in MUSIC_TRACKS {
+        assets.music.insert(path, asset_server.load(path));
+    }
+    for kind in SfxKind::ALL {
+        assets.sfx.insert(kind, asset_server.load(kind.path()));
+    }
+}
+
+// Fruit icons come from the catalogue, so they can only be queued once it has loaded
+pub fn load_fruit_icons(
+    asset_server: Res<AssetServer>,
+    catalogue: Res<FruitCatalogue>,
+    mut assets: ResMut<GameAssets>,
+) {
+    if !catalogue.is_changed() {
+        return;
+    }
+    for fruit in catalogue.fruits.iter() {
+        if !assets.fruit_icons.contains_key(&fruit.icon) {
+            let handle = asset_server.load(fruit.icon.clone());
+            assets.fruit_icons.insert(fruit.icon.clone(), handle);
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use bevy::{
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::GameAssets,
+    focus,
+    game_mod::{GameData, PauseTimer},
+    input::{Action, Actions},
+    leaderboard::Editing,
+    main_menu::SoundEffect,
+    options::Rebinding,
+    storage, AppState,
+};
+
+const AUDIO_SETTINGS_SAVE: &str = "audio";
+// Seconds for one track to fade out while the next fades in
+const CROSSFADE_SECS: f32 = 1.5;
+const ROUND_MUSIC: &str = "music/Bees.ogg";
+// Every track state_music can ask for
+pub const MUSIC_TRACKS: [&str; 5] = [
+    "music/Dandelions.ogg",
+    ROUND_MUSIC,
+    "music/Petunia.ogg",
+    "music/Rain.ogg",
+    "music/Path.ogg",
+];
+// Round music speed up per level, capped at MAX_LEVEL_SPEEDUP, and in the final seconds
+const LEVEL_SPEEDUP: f32 = 0.02;
+const MAX_LEVEL_SPEEDUP: f32 = 0.2;
+const URGENCY_SECS: f32 = 2.0;
+const URGENCY_SPEEDUP: f32 = 0.15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Sfx,
+}
+
+impl VolumeChannel {
+    pub const ALL: [VolumeChannel; 3] = [
+        VolumeChannel::Master,
+        VolumeChannel::Music,
+        VolumeChannel::Sfx,
+    ];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            VolumeChannel::Master => "audio.master",
+            VolumeChannel::Music => "audio.music",
+            VolumeChannel::Sfx => "audio.effects",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioToggle {
+    Mute,
+    AdaptiveMusic,
+    TimerTicks,
+}
+
+impl AudioToggle {
+    pub const ALL: [AudioToggle; 3] = [
+        AudioToggle::Mute,
+        AudioToggle::AdaptiveMusic,
+        AudioToggle::TimerTicks,
+    ];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            AudioToggle::Mute => "audio.mute",
+            AudioToggle::AdaptiveMusic => "audio.adaptive_music",
+            AudioToggle::TimerTicks => "audio.timer_ticks",
+        }
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub muted: bool,
+    // Round music speeds up with the level and as the timer runs out
+    pub adaptive_music: bool,
+    // Ticks through the last second of a round
+    pub timer_ticks: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.5,
+            sfx: 1.0,
+            muted: false,
+            adaptive_music: true,
+            timer_ticks: true,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn get(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Master => self.master,
+            VolumeChannel::Music => self.music,
+            VolumeChannel::Sfx => self.sfx,
+        }
+    }
+
+    pub fn set(&mut self, channel: VolumeChannel, value: f32) {
+        let value = value.clamp(0.0, 1.0);
+        match channel {
+            VolumeChannel::Master => self.master = value,
+            VolumeChannel::Music => self.music = value,
+            VolumeChannel::Sfx => self.sfx = value,
+        }
+    }
+
+    pub fn enabled(&self, toggle: AudioToggle) -> bool {
+        match toggle {
+            AudioToggle::Mute => self.muted,
+            AudioToggle::AdaptiveMusic => self.adaptive_music,
+            AudioToggle::TimerTicks => self.timer_ticks,
+        }
+    }
+
+    pub fn flip(&mut self, toggle: AudioToggle) {
+        match toggle {
+            AudioToggle::Mute => self.muted = !self.muted,
+            AudioToggle::AdaptiveMusic => self.adaptive_music = !self.adaptive_music,
+            AudioToggle::TimerTicks => self.timer_ticks = !self.timer_ticks,
+        }
+    }
+
+    /// Final volume of a music track, `gain` evens out tracks mastered louder than others
+    pub fn music_volume(&self, gain: f32) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master * self.music * gain
+        }
+    }
+
+    pub fn sfx_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master * self.sfx
+        }
+    }
+
+    pub fn save(&self) {
+        storage::save(AUDIO_SETTINGS_SAVE, self);
+    }
+}
+
+/// Background music, owned by the music controller rather than any one screen
+#[derive(Component)]
+pub struct MusicTrack {
+    pub path: &'static str,
+    // How loud the track sits relative to the music volume
+    pub gain: f32,
+    // Crossfade position, 0 silent to 1 full volume
+    pub level: f32,
+    pub fading_out: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SfxKind {
+    Select,
+    Back,
+    Answer,
+    Correct,
+    Incorrect,
+    NewChar,
+    Tick,
+}
+
+impl SfxKind {
+    pub const ALL: [SfxKind; 7] = [
+        SfxKind::Select,
+        SfxKind::Back,
+        SfxKind::Answer,
+        SfxKind::Correct,
+        SfxKind::Incorrect,
+        SfxKind::NewChar,
+        SfxKind::Tick,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            SfxKind::Select => "music/Select.ogg",
+            SfxKind::Back => "music/Back.ogg",
+            SfxKind::Answer => "music/Answer.ogg",
+            SfxKind::Correct => "music/Correct.ogg",
+            SfxKind::Incorrect => "music/Incorrect.ogg",
+            SfxKind::NewChar => "music/NewChar.ogg",
+            // No tick sample, the select blip played fast stands in for one
+            SfxKind::Tick => "music/Select.ogg",
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            SfxKind::Tick => 2.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Plays a sound effect, repeats of the same sound within a frame are dropped
+#[derive(Event, Clone, Copy)]
+pub struct PlaySfx(pub SfxKind);
+
+pub fn play_sfx(
+    mut commands: Commands,
+    mut events: EventReader<PlaySfx>,
+    assets: Res<GameAssets>,
+    audio_settings: Res<AudioSettings>,
+) {
+    let mut played: Vec<SfxKind> = Vec::new();
+    for PlaySfx(kind) in events.read() {
+        if played.contains(kind) {
+            continue;
+        }
+        played.push(*kind);
+        commands.spawn((
+            AudioBundle {
+                source: assets.sfx(*kind),
+                settings: PlaybackSettings {
+                    mode: PlaybackMode::Despawn,
+                    volume: Volume::new_relative(audio_settings.sfx_volume()),
+                    speed: kind.speed(),
+                    ..Default::default()
+                },
+            },
+            SoundEffect,
+        ));
+    }
+}
+
+pub fn load_audio_settings(mut audio_settings: ResMut<AudioSettings>) {
+    if let Some(saved) = storage::load::<AudioSettings>(AUDIO_SETTINGS_SAVE) {
+        *audio_settings = saved;
+    }
+}
+
+pub fn toggle_mute(
+    actions: Actions,
+    rebinding: Res<Rebinding>,
+    editing_query: Query<(), With<Editing>>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    if focus::keyboard_blocked(&rebinding, &editing_query) {
+        return;
+    }
+    if actions.just_released(Action::Mute) {
+        audio_settings.flip(AudioToggle::Mute);
+        audio_settings.save();
+    }
+}
+
+// Carry volume changes over to effects already playing, music picks them up in fade_music
+pub fn apply_audio_settings(
+    audio_settings: Res<AudioSettings>,
+    sfx_query: Query<&AudioSink, With<SoundEffect>>,
+) {
+    if !audio_settings.is_changed() {
+        return;
+    }
+    for sink in sfx_query.iter() {
+        sink.set_volume(audio_settings.sfx_volume());
+    }
+}
+
+// Track for each state and its gain, states sharing a track keep it playing
+fn state_music(state: &AppState) -> Option<(&'static str, f32)> {
+    match state {
+        AppState::MainMenu | AppState::GameOver | AppState::Results => {
+            Some(("music/Dandelions.ogg", 1.0))
+        }
+        AppState::StartRound | AppState::Pause | AppState::PauseMenu | AppState::ShowResults => {
+            Some((ROUND_MUSIC, 1.0))
+        }
+        AppState::Leaderboard | AppState::Options | AppState::Display | AppState::Stats => {
+            Some(("music/Petunia.ogg", 1.0))
+        }
+        AppState::Settings => Some(("music/Rain.ogg", 0.4)),
+        AppState::HowToPlay => Some(("music/Path.ogg", 1.0)),
+        AppState::LoadingScreen | AppState::QuitGame => None,
+    }
+}
+
+pub fn play_state_music(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    state: Res<State<AppState>>,
+    mut track_query: Query<&mut MusicTrack>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    let wanted = state_music(state.get());
+
+    let mut playing = false;
+    for mut track in track_query.iter_mut() {
+        if !playing && wanted.is_some_and(|(path, _)| path == track.path) {
+            // Also brings back a track that was on its way out
+            track.fading_out = false;
+            playing = true;
+        } else {
+            track.fading_out = true;
+        }
+    }
+
+    if let (false, Some((path, gain))) = (playing, wanted) {
+        commands.spawn((
+            AudioBundle {
+                source: assets.music(path),
+                settings: PlaybackSettings {
+                    mode: PlaybackMode::Loop,
+                    volume: Volume::new_relative(0.0),
+                    ..Default::default()
+                },
+            },
+            MusicTrack {
+                path,
+                gain,
+                level: 0.0,
+                fading_out: false,
+            },
+        ));
+    }
+}
+
+pub fn fade_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    audio_settings: Res<AudioSettings>,
+    mut track_query: Query<(Entity, &mut MusicTrack, Option<&AudioSink>)>,
+) {
+    let step = time.delta_seconds() / CROSSFADE_SECS;
+    for (entity, mut track, sink) in track_query.iter_mut() {
+        if track.fading_out {
+            track.level -= step;
+            if track.level <= 0.0 {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        } else if track.level < 1.0 {
+            track.level = (track.level + step).min(1.0);
+        }
+        if let Some(sink) = sink {
+            sink.set_volume(audio_settings.music_volume(track.gain) * track.level);
+        }
+    }
+}
+
+pub fn adapt_round_music(
+    state: Res<State<AppState>>,
+    game_data: Res<GameData>,
+    timer: Res<PauseTimer>,
+    audio_settings: Res<AudioSettings>,
+    track_query: Query<(&MusicTrack, &AudioSink)>,
+) {
+    let mut speed = 1.0;
+    if audio_settings.adaptive_music {
+        speed += (game_data.level() - 1) as f32 * LEVEL_SPEEDUP;
+        speed = speed.min(1.0 + MAX_LEVEL_SPEEDUP);
+        // Only while the player is guessing, the timer sits still everywhere else
+        if *state.get() == AppState::Pause {
+            let remaining = timer.pause_timer.remaining_secs();
+            speed += URGENCY_SPEEDUP * (1.0 - remaining / URGENCY_SECS).max(0.0);
+        }
+    }
+    for (track, sink) in track_query.iter() {
+        let track_speed = if track.path == ROUND_MUSIC {
+            speed
+        } else {
+            1.0
+        };
+        if sink.speed() != track_speed {
+            sink.set_speed(track_speed);
+        }
+    }
+}
+
+// A quick tick on every quarter of the last second
+pub fn tick_last_second(
+    mut sfx: EventWriter<PlaySfx>,
+    audio_settings: Res<AudioSettings>,
+    timer: Res<PauseTimer>,
+    mut last_remaining: Local<f32>,
+) {
+    let remaining = timer.pause_timer.remaining_secs();
+    let crossed = (remaining * 4.0).ceil() < (*last_remaining * 4.0).ceil();
+    if audio_settings.timer_ticks && crossed && remaining > 0.0 && remaining < 1.0 {
+        sfx.send(PlaySfx(SfxKind::Tick));
+    }
+    *last_remaining = remaining;
+}
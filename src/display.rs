@@ -0,0 +1,364 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow, WindowMode, WindowResolution},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::{PlaySfx, SfxKind},
+    focus::DefaultFocus,
+    input::{Action, Actions},
+    locale::Strings,
+    storage,
+    ui::{spawn_button, ButtonBuilder, ColorRole, TextRole, Theme},
+    AppState,
+};
+
+const DISPLAY_SAVE: &str = "display";
+// Window sizes the resolution button steps through
+pub const RESOLUTIONS: [(u32, u32); 5] = [
+    (1280, 720),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+// Frame rate caps the FPS button steps through, None leaves it to vsync
+pub const FPS_CAPS: [Option<u32>; 4] = [None, Some(30), Some(60), Some(144)];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [
+        DisplayMode::Windowed,
+        DisplayMode::Borderless,
+        DisplayMode::Fullscreen,
+    ];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "display.windowed",
+            DisplayMode::Borderless => "display.borderless",
+            DisplayMode::Fullscreen => "display.fullscreen",
+        }
+    }
+
+    pub fn next(&self) -> DisplayMode {
+        let index = DisplayMode::ALL
+            .iter()
+            .position(|mode| mode == self)
+            .unwrap_or(0);
+        DisplayMode::ALL[(index + 1) % DisplayMode::ALL.len()]
+    }
+
+    fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub mode: DisplayMode,
+    // Only used while windowed, the fullscreen modes take the monitor's size
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    pub fps_cap: Option<u32>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            mode: DisplayMode::Windowed,
+            resolution: RESOLUTIONS[0],
+            vsync: true,
+            fps_cap: None,
+        }
+    }
+}
+
+impl DisplaySettings {
+    // Read before the app is built so the window opens with them
+    pub fn load() -> Self {
+        storage::load::<DisplaySettings>(DISPLAY_SAVE).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save(DISPLAY_SAVE, self);
+    }
+
+    /// Primary window for `WindowPlugin`
+    pub fn window(&self) -> Window {
+        let mut window = Window::default();
+        self.apply(&mut window);
+        window
+    }
+
+    fn apply(&self, window: &mut Window) {
+        window.mode = self.mode.window_mode();
+        window.present_mode = self.present_mode();
+        if self.mode == DisplayMode::Windowed {
+            let (width, height) = self.resolution;
+            window.resolution = WindowResolution::new(width as f32, height as f32);
+        }
+    }
+
+    fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    pub fn next_resolution(&mut self) {
+        let index = RESOLUTIONS
+            .iter()
+            .position(|resolution| *resolution == self.resolution)
+            .map_or(0, |index| index + 1);
+        self.resolution = RESOLUTIONS[index % RESOLUTIONS.len()];
+    }
+
+    pub fn next_fps_cap(&mut self) {
+        let index = FPS_CAPS
+            .iter()
+            .position(|cap| *cap == self.fps_cap)
+            .unwrap_or(0);
+        self.fps_cap = FPS_CAPS[(index + 1) % FPS_CAPS.len()];
+    }
+}
+
+#[derive(Component)]
+pub struct DisplayScreen;
+
+#[derive(Component)]
+pub enum DisplayButton {
+    Back,
+    Mode,
+    Resolution,
+    Vsync,
+    FpsCap,
+}
+
+impl DisplayButton {
+    fn label(&self, strings: &Strings, display: &DisplaySettings) -> String {
+        match self {
+            DisplayButton::Back => strings.get("common.back"),
+            DisplayButton::Mode => {
+                strings.setting("display.mode", strings.get(display.mode.label_key()))
+            }
+            DisplayButton::Resolution => strings.setting(
+                "display.resolution",
+                format!("{}x{}", display.resolution.0, display.resolution.1),
+            ),
+            DisplayButton::Vsync => strings.setting("display.vsync", strings.on_off(display.vsync)),
+            DisplayButton::FpsCap => strings.setting(
+                "display.fps_cap",
+                match display.fps_cap {
+                    Some(fps) => fps.to_string(),
+                    None => strings.get("display.unlimited"),
+                },
+            ),
+        }
+    }
+}
+
+pub fn setup_ui(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    display: Res<DisplaySettings>,
+    strings: Strings,
+) {
+    // Spawn Title Text
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                strings.get("display.title"),
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 64.0,
+                    color: theme.text,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            style: Style {
+                top: Val::Px(-216.0),
+                justify_self: JustifySelf::Center,
+                align_self: AlignSelf::Center,
+                ..default()
+            },
+            ..default()
+        },
+        DisplayScreen,
+        TextRole(ColorRole::Text),
+    ));
+
+    // Spawn Settings
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // horizontally center child text
+                    justify_self: JustifySelf::Center,
+                    // vertically center child text
+                    align_self: AlignSelf::Center,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    top: Val::Px(36.0),
+                    ..default()
+                },
+                ..default()
+            },
+            DisplayScreen,
+        ))
+        .with_children(|parent| {
+            for display_button in [
+                DisplayButton::Mode,
+                DisplayButton::Resolution,
+                DisplayButton::Vsync,
+                DisplayButton::FpsCap,
+            ] {
+                let label = display_button.label(&strings, &display);
+                ButtonBuilder::new(label, (display_button, DefaultFocus))
+                    .size(440., 50.)
+                    .font_size(32.)
+                    .margin(UiRect::top(Val::Px(12.0)))
+                    .spawn(parent, &theme);
+            }
+        });
+
+    // Spawn Back Button
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::Start,
+                    ..default()
+                },
+                ..default()
+            },
+            DisplayScreen,
+        ))
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                &theme,
+                strings.get("common.back"),
+                DisplayButton::Back,
+            );
+        });
+}
+
+pub fn interact_button(
+    interaction_query: Query<(&Interaction, &DisplayButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut display: ResMut<DisplaySettings>,
+    mut next_state: ResMut<NextState<AppState>>,
+    actions: Actions,
+    strings: Strings,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    // Keyboard Input
+    if actions.just_released(Action::Back) {
+        sfx.send(PlaySfx(SfxKind::Back));
+        next_state.set(AppState::Options);
+    }
+    // Buttons
+    for (interaction, display_button, children) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match display_button {
+            DisplayButton::Back => {
+                sfx.send(PlaySfx(SfxKind::Back));
+                next_state.set(AppState::Options);
+                continue;
+            }
+            DisplayButton::Mode => display.mode = display.mode.next(),
+            DisplayButton::Resolution => display.next_resolution(),
+            DisplayButton::Vsync => display.vsync = !display.vsync,
+            DisplayButton::FpsCap => display.next_fps_cap(),
+        }
+        display.save();
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = display_button.label(&strings, &display);
+            }
+        }
+    }
+}
+
+pub fn apply_display_settings(
+    display: Res<DisplaySettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    // The window already opened with the saved settings
+    if !display.is_changed() || display.is_added() {
+        return;
+    }
+    for mut window in window_query.iter_mut() {
+        display.apply(&mut window);
+    }
+}
+
+// Sleeps out the rest of each frame's budget, browsers pace frames themselves
+pub fn limit_frame_rate(display: Res<DisplaySettings>, mut frame_start: Local<Option<Instant>>) {
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+    if let (Some(fps), Some(start)) = (display.fps_cap, *frame_start) {
+        let budget = Duration::from_secs_f64(1.0 / fps as f64);
+        let elapsed = start.elapsed();
+        if elapsed < budget {
+            std::thread::sleep(budget - elapsed);
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
+
+pub fn clear_shapes(mut commands: Commands, mut query: Query<Entity, With<DisplayScreen>>) {
+    for entity in query.iter_mut() {
+        if let Some(entity) = commands.get_entity(entity) {
+            entity.despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolution_steps_through_the_list_and_wraps() {
+        let mut display = DisplaySettings::default();
+        for expected in RESOLUTIONS.iter().skip(1).chain(RESOLUTIONS.iter().take(1)) {
+            display.next_resolution();
+            assert_eq!(display.resolution, *expected);
+        }
+    }
+
+    #[test]
+    fn unknown_resolution_goes_back_to_the_first() {
+        let mut display = DisplaySettings {
+            resolution: (1024, 768),
+            ..default()
+        };
+        display.next_resolution();
+        assert_eq!(display.resolution, RESOLUTIONS[0]);
+    }
+}
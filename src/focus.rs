@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+
+use crate::{
+    input::{Action, Actions},
+    leaderboard::Editing,
+    options::Rebinding,
+    ui::Theme,
+};
+
+// How far the stick has to be pushed before it moves focus, and how far
+// back it has to come before it can move focus again
+const STICK_PUSH: f32 = 0.6;
+const STICK_CENTER: f32 = 0.3;
+
+/// Button that keyboard or gamepad navigation is currently on
+#[derive(Component)]
+pub struct Focused;
+
+/// Buttons navigation starts from when nothing is focused yet
+#[derive(Component)]
+pub struct DefaultFocus;
+
+enum Step {
+    Direction(Vec2),
+    Next,
+    Previous,
+}
+
+// Button pressed through focus last frame, released again on the next one
+#[derive(Resource, Default)]
+pub struct FocusActivation {
+    pub entity: Option<Entity>,
+}
+
+fn keyboard_step(keyboard_input: &Input<KeyCode>) -> Option<Step> {
+    if keyboard_input.just_pressed(KeyCode::Tab) {
+        let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        return Some(if shift { Step::Previous } else { Step::Next });
+    }
+    [
+        (KeyCode::Up, Vec2::NEG_Y),
+        (KeyCode::Down, Vec2::Y),
+        (KeyCode::Left, Vec2::NEG_X),
+        (KeyCode::Right, Vec2::X),
+    ]
+    .into_iter()
+    .find(|(key, _)| keyboard_input.just_pressed(*key))
+    .map(|(_, direction)| Step::Direction(direction))
+}
+
+// Typing into a leaderboard field or picking a new key binding needs the keys to itself
+pub fn keyboard_blocked(rebinding: &Rebinding, editing_query: &Query<(), With<Editing>>) -> bool {
+    rebinding.action.is_some() || !editing_query.is_empty()
+}
+
+fn gamepad_direction(
+    gamepads: &Gamepads,
+    gamepad_input: &Input<GamepadButton>,
+    axes: &Axis<GamepadAxis>,
+    stick_centered: &mut bool,
+) -> Option<Vec2> {
+    let mut direction = None;
+    let mut stick = Vec2::ZERO;
+    for gamepad in gamepads.iter() {
+        // UI space grows downwards
+        let dpad = [
+            (GamepadButtonType::DPadUp, Vec2::NEG_Y),
+            (GamepadButtonType::DPadDown, Vec2::Y),
+            (GamepadButtonType::DPadLeft, Vec2::NEG_X),
+            (GamepadButtonType::DPadRight, Vec2::X),
+        ];
+        for (button_type, dpad_direction) in dpad {
+            if gamepad_input.just_pressed(GamepadButton::new(gamepad, button_type)) {
+                direction = Some(dpad_direction);
+            }
+        }
+        let x = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.0);
+        let y = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.0);
+        if Vec2::new(x, y).length() > stick.length() {
+            stick = Vec2::new(x, -y);
+        }
+    }
+
+    if stick.length() < STICK_CENTER {
+        *stick_centered = true;
+    } else if stick.length() > STICK_PUSH && *stick_centered {
+        *stick_centered = false;
+        if direction.is_none() {
+            direction = Some(if stick.x.abs() > stick.y.abs() {
+                Vec2::new(stick.x.signum(), 0.0)
+            } else {
+                Vec2::new(0.0, stick.y.signum())
+            });
+        }
+    }
+    direction
+}
+
+pub fn navigate_focus(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    rebinding: Res<Rebinding>,
+    editing_query: Query<(), With<Editing>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut stick_centered: Local<bool>,
+    mut button_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &InheritedVisibility,
+            &mut Interaction,
+            Option<&Focused>,
+        ),
+        With<Button>,
+    >,
+    default_query: Query<(), With<DefaultFocus>>,
+) {
+    // Moving the mouse hands control back to the cursor
+    if cursor_moved.read().count() > 0 {
+        for (entity, _, _, mut interaction, focused) in button_query.iter_mut() {
+            if focused.is_some() {
+                commands.entity(entity).remove::<Focused>();
+                interaction.set_changed();
+            }
+        }
+        return;
+    }
+
+    let gamepad_step = gamepad_direction(&gamepads, &gamepad_input, &axes, &mut stick_centered)
+        .map(Step::Direction);
+    let step = if keyboard_blocked(&rebinding, &editing_query) {
+        gamepad_step
+    } else {
+        keyboard_step(&keyboard_input).or(gamepad_step)
+    };
+    let Some(step) = step else {
+        return;
+    };
+
+    // Visible buttons in reading order
+    let mut buttons: Vec<(Entity, Vec2, bool)> = button_query
+        .iter()
+        .filter(|(_, _, visibility, _, _)| visibility.get())
+        .map(|(entity, transform, _, _, focused)| {
+            (
+                entity,
+                transform.translation().truncate(),
+                focused.is_some(),
+            )
+        })
+        .collect();
+    buttons.sort_by(|a, b| (a.1.y, a.1.x).partial_cmp(&(b.1.y, b.1.x)).unwrap());
+
+    let current = buttons.iter().position(|(_, _, focused)| *focused);
+    let next = match (current, step) {
+        // Closest button in the pressed direction, preferring ones in line with the current one
+        (Some(index), Step::Direction(direction)) => {
+            let (current, position, _) = buttons[index];
+            buttons
+                .iter()
+                .filter(|(entity, _, _)| *entity != current)
+                .filter_map(|(entity, other, _)| {
+                    let offset = *other - position;
+                    let along = offset.dot(direction);
+                    let across = offset.perp_dot(direction).abs();
+                    (along > 1.0).then_some((*entity, along + across * 2.0))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(entity, _)| entity)
+        }
+        (Some(index), Step::Next) => Some(buttons[(index + 1) % buttons.len()].0),
+        (Some(index), Step::Previous) => {
+            Some(buttons[(index + buttons.len() - 1) % buttons.len()].0)
+        }
+        // Nothing focused yet, start from the first default button or the top left one
+        (None, _) => buttons
+            .iter()
+            .find(|(entity, _, _)| default_query.contains(*entity))
+            .or(buttons.first())
+            .map(|(entity, _, _)| *entity),
+    };
+
+    if let Some(entity) = next {
+        if let Some(index) = current {
+            let previous = buttons[index].0;
+            commands.entity(previous).remove::<Focused>();
+            // Let the owning screen redraw its normal border
+            if let Ok((_, _, _, mut interaction, _)) = button_query.get_mut(previous) {
+                interaction.set_changed();
+            }
+        }
+        commands.entity(entity).insert(Focused);
+    }
+}
+
+pub fn activate_focus(
+    actions: Actions,
+    keyboard_input: Res<Input<KeyCode>>,
+    rebinding: Res<Rebinding>,
+    editing_query: Query<(), With<Editing>>,
+    mut activation: ResMut<FocusActivation>,
+    mut button_query: Query<(Entity, &mut Interaction, Option<&Focused>), With<Button>>,
+) {
+    // Release last frame's press so the button can be pressed again
+    if let Some(entity) = activation.entity.take() {
+        if let Ok((_, mut interaction, _)) = button_query.get_mut(entity) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+
+    // Keys activate on release so the press can't leak into whatever the button opens
+    let keyboard_activate = !keyboard_blocked(&rebinding, &editing_query)
+        && (actions.key_just_released(Action::Confirm)
+            || keyboard_input.just_released(KeyCode::Space));
+    if !keyboard_activate && !actions.gamepad_just_pressed(Action::Confirm) {
+        return;
+    }
+    for (entity, mut interaction, focused) in button_query.iter_mut() {
+        if focused.is_some() {
+            *interaction = Interaction::Pressed;
+            activation.entity = Some(entity);
+        }
+    }
+}
+
+pub fn draw_focus(
+    theme: Res<Theme>,
+    mut focus_query: Query<&mut BorderColor, (With<Focused>, With<Button>)>,
+) {
+    for mut border_color in focus_query.iter_mut() {
+        if border_color.0 != theme.emphasis {
+            *border_color = theme.emphasis.into();
+        }
+    }
+}
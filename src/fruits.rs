@@ -0,0 +1,187 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::{BoxedFuture, HashSet},
+};
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+
+// Root fruit pack, any further packs are listed in its `packs` field
+pub const FRUIT_CATALOGUE: &str = "fruits/base.fruits.json";
+
+pub struct FruitPlugin;
+impl Plugin for FruitPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<FruitPack>()
+            .init_asset_loader::<FruitPackLoader>()
+            .init_resource::<FruitCatalogue>()
+            .add_systems(Startup, load_catalogue)
+            .add_systems(Update, update_catalogue);
+    }
+}
+
+/// Identifies a fruit from the catalogue by its `id`
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FruitType(pub String);
+
+impl FruitType {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct FruitDefinition {
+    pub id: String,
+    pub name: String,
+    pub icon: String,
+    pub color: [u8; 3],
+    // Personal best level needed before the fruit can be picked
+    #[serde(default)]
+    pub unlock_level: i64,
+}
+
+impl FruitDefinition {
+    pub fn fruit_type(&self) -> FruitType {
+        FruitType(self.id.clone())
+    }
+
+    pub fn color(&self) -> Color {
+        Color::rgb_u8(self.color[0], self.color[1], self.color[2])
+    }
+
+    pub fn is_unlocked(&self, best_score: i64) -> bool {
+        best_score >= self.unlock_level - 1
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct FruitPack {
+    #[serde(default)]
+    pub fruits: Vec<FruitDefinition>,
+    // Asset paths of further packs to pull into the catalogue
+    #[serde(default)]
+    pub packs: Vec<String>,
+    #[serde(skip)]
+    pub pack_handles: Vec<Handle<FruitPack>>,
+}
+
+#[derive(Default)]
+pub struct FruitPackLoader;
+
+impl AssetLoader for FruitPackLoader {
+    type Asset = FruitPack;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<FruitPack, std::io::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut pack = serde_json::from_slice::<FruitPack>(&bytes)?;
+            for path in pack.packs.iter() {
+                pack.pack_handles.push(load_context.load(path.clone()));
+            }
+            Ok(pack)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["fruits.json"]
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct FruitCatalogue {
+    pub root: Handle<FruitPack>,
+    pub fruits: Vec<FruitDefinition>,
+}
+
+impl FruitCatalogue {
+    pub fn get(&self, fruit_type: &FruitType) -> Option<&FruitDefinition> {
+        self.fruits.iter().find(|fruit| fruit.id == fruit_type.0)
+    }
+
+    /// Icon path for a fruit, falling back to the old `icons/{id}.png` convention
+    pub fn icon(&self, fruit_type: &FruitType) -> String {
+        match self.get(fruit_type) {
+            Some(fruit) => fruit.icon.clone(),
+            None => format!("icons/{}.png", fruit_type.0),
+        }
+    }
+
+    pub fn name(&self, fruit_type: &FruitType) -> String {
+        match self.get(fruit_type) {
+            Some(fruit) => fruit.name.clone(),
+            None => fruit_type.0.clone(),
+        }
+    }
+
+    /// Every pack reached from the root so far, each once, in the order their fruit are added.
+    /// Packs still loading are included but their own `packs` can't be followed yet
+    pub fn pack_handles(&self, packs: &Assets<FruitPack>) -> Vec<Handle<FruitPack>> {
+        let mut handles = Vec::new();
+        // Packs already walked are skipped so packs listing each other can't loop
+        let mut visited: HashSet<AssetId<FruitPack>> = HashSet::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(handle) = pending.pop() {
+            if !visited.insert(handle.id()) {
+                continue;
+            }
+            if let Some(pack) = packs.get(&handle) {
+                pending.extend(pack.pack_handles.iter().rev().cloned());
+            }
+            handles.push(handle);
+        }
+        handles
+    }
+
+    pub fn unlocked(&self, best_score: i64) -> impl Iterator<Item = &FruitDefinition> {
+        self.fruits
+            .iter()
+            .filter(move |fruit| fruit.is_unlocked(best_score))
+    }
+
+    /// Two distinct unlocked fruits, or None when fewer than two are unlocked
+    pub fn random_pair(&self, best_score: i64) -> Option<(FruitType, FruitType)> {
+        let unlocked: Vec<&FruitDefinition> = self.unlocked(best_score).collect();
+        let mut pair = unlocked.choose_multiple(&mut rand::thread_rng(), 2);
+        match (pair.next(), pair.next()) {
+            (Some(a), Some(b)) => Some((a.fruit_type(), b.fruit_type())),
+            _ => None,
+        }
+    }
+}
+
+pub fn load_catalogue(asset_server: Res<AssetServer>, mut catalogue: ResMut<FruitCatalogue>) {
+    catalogue.root = asset_server.load(FRUIT_CATALOGUE);
+}
+
+pub fn update_catalogue(
+    mut events: EventReader<AssetEvent<FruitPack>>,
+    packs: Res<Assets<FruitPack>>,
+    mut catalogue: ResMut<FruitCatalogue>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    // The first definition of an id wins
+    let mut fruits: Vec<FruitDefinition> = Vec::new();
+    for handle in catalogue.pack_handles(&packs) {
+        let Some(pack) = packs.get(&handle) else {
+            continue;
+        };
+        for fruit in pack.fruits.iter() {
+            if !fruits.iter().any(|known| known.id == fruit.id) {
+                fruits.push(fruit.clone());
+            }
+        }
+    }
+    catalogue.fruits = fruits;
+}
@@ -1,18 +1,23 @@
-use bevy::{
-    audio::{PlaybackMode, Volume},
-    prelude::*,
-};
+use bevy::prelude::*;
 use bevy_tweening::{
-    lens::{TransformRotationLens, UiPositionLens},
+    lens::{TransformRotationLens, TransformScaleLens, UiPositionLens},
     Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween,
 };
 use rand::Rng;
 use std::time::Duration;
 
 use crate::{
-    leaderboard::UpdateRoundEvent,
-    main_menu::{SoundEffect, BASIL_GREEN, FONT, OLIVE_GREEN},
-    settings::{GameSettings, Villagers},
+    accessibility::{spawn_fruit_mark, AccessibilitySettings, MarkLetter},
+    assets::GameAssets,
+    audio::{PlaySfx, SfxKind},
+    fruits::{FruitCatalogue, FruitType},
+    input::{Action, Actions},
+    layout::ScreenLayout,
+    leaderboard::{UpdateRoundEvent, User},
+    locale::Strings,
+    settings::{FruitSlot, GameSettings, Villagers},
+    stats::RoundFinished,
+    ui::{ButtonBorder, ButtonBuilder, ColorRole, SectionRoles, TextRole, Theme},
     AppState,
 };
 
@@ -26,6 +31,47 @@ pub struct GameData {
     time_elapsed: Duration,
     fruit_array: Vec<FruitType>,
     file_array: Vec<String>,
+    fruit_a: FruitType,
+    fruit_b: FruitType,
+    run: RunStats,
+    // Set by the pause menu, the run still ends through GameOver so its score is uploaded
+    exit: RunExit,
+}
+
+/// Where game_over heads once the run is recorded
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum RunExit {
+    #[default]
+    Results,
+    Restart,
+    MainMenu,
+}
+
+/// How the current run went, kept after it ends for the results screen
+#[derive(Clone, Default)]
+pub struct RunStats {
+    pub level: i32,
+    pub score: i64,
+    pub answered: u32,
+    pub correct: u32,
+    // Missed rounds count against accuracy but not reaction time
+    pub timed_out: u32,
+    pub reaction_total: Duration,
+    pub villagers_unlocked: u32,
+    pub new_best: bool,
+    // The miss that ended the run, None when it was ended from the pause menu
+    pub ending: Option<Result>,
+}
+
+impl RunStats {
+    pub fn accuracy(&self) -> Option<f32> {
+        let rounds = self.answered + self.timed_out;
+        (rounds > 0).then(|| self.correct as f32 / rounds as f32)
+    }
+
+    pub fn average_reaction(&self) -> Option<Duration> {
+        (self.answered > 0).then(|| self.reaction_total / self.answered)
+    }
 }
 
 impl Default for GameData {
@@ -39,21 +85,39 @@ impl Default for GameData {
             time_elapsed: Duration::new(0, 0),
             file_array: Default::default(),
             fruit_array: Default::default(),
+            fruit_a: FruitType::new("apple"),
+            fruit_b: FruitType::new("pear"),
+            run: RunStats::default(),
+            exit: RunExit::Results,
         }
     }
 }
 
-#[derive(Component, Clone, Debug, PartialEq)]
-pub enum FruitType {
-    Apple,
-    Pear,
-    Orange,
-    Strawberry,
+impl GameData {
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    pub fn run(&self) -> &RunStats {
+        &self.run
+    }
+
+    /// Ends the run, but game_over heads straight into a new one
+    pub fn restart(&mut self) {
+        self.exit = RunExit::Restart;
+    }
+
+    /// Ends the run, but game_over skips the results screen for the main menu
+    pub fn quit(&mut self) {
+        self.exit = RunExit::MainMenu;
+    }
 }
 
 #[derive(Resource)]
 pub struct ResultTimer {
     pub result_timer: Timer,
+    // Longer, so there's time to read the counts after a miss
+    pub reveal_timer: Timer,
 }
 
 #[derive(Resource)]
@@ -67,16 +131,19 @@ pub enum AnswerButton {
     O,
 }
 
-#[derive(Component, Default)]
+#[derive(Component, Clone, Copy, Default, PartialEq, Eq)]
 pub enum Result {
     #[default]
     Correct,
     Incorrect,
+    TimedOut,
 }
 
 #[derive(Component)]
 pub enum GridIdentifier {
     Grid,
+    // The fruit being counted this round
+    Fruit,
 }
 
 #[derive(Component)]
@@ -84,11 +151,17 @@ pub struct RoundTimer;
 
 pub fn play_game(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
     mut next_state: ResMut<NextState<AppState>>,
     mut game_data: ResMut<GameData>,
     game_settings: Res<GameSettings>,
+    catalogue: Res<FruitCatalogue>,
+    user: Res<User>,
     timer: Res<PauseTimer>,
+    accessibility: Res<AccessibilitySettings>,
+    layout: Res<ScreenLayout>,
+    strings: Strings,
 ) {
     game_data.fruit_array.clear();
     game_data.file_array.clear();
@@ -96,32 +169,38 @@ pub fn play_game(
     game_data.time_elapsed = Duration::new(0, 0);
     game_data.exes = 0;
     game_data.os = 0;
+    game_data.result = Result::Correct;
+    // Every run starts back on the first level
+    if game_data.level == 1 {
+        game_data.run = RunStats::default();
+    }
     let mut rng = rand::thread_rng();
 
     // Spawn Level Text
-    let title = format!("Level {}", game_data.level);
+    let title = strings.format("round.level", &[("level", game_data.level.to_string())]);
     println!("{:?}", game_data.level);
 
-    commands.spawn({
+    commands.spawn((
         TextBundle {
             text: Text::from_section(
                 title,
                 TextStyle {
-                    font: asset_server.load(FONT),
+                    font: theme.font.clone(),
                     font_size: 64.0,
-                    color: OLIVE_GREEN,
+                    color: theme.text,
                 },
             )
             .with_alignment(TextAlignment::Center),
             style: Style {
-                top: Val::Vh(-30.0),
+                top: Val::Px(-216.0),
                 justify_self: JustifySelf::Center,
                 align_self: AlignSelf::Center,
                 ..default()
             },
             ..default()
-        }
-    });
+        },
+        TextRole(ColorRole::Text),
+    ));
     // Spawn Timer Text
 
     let timer: String = format!("{:?}", timer.pause_timer.remaining_secs());
@@ -132,9 +211,9 @@ pub fn play_game(
                 text: Text::from_section(
                     timer,
                     TextStyle {
-                        font: asset_server.load(FONT),
+                        font: theme.font.clone(),
                         font_size: 48.0,
-                        color: OLIVE_GREEN,
+                        color: theme.text,
                     },
                 )
                 .with_alignment(TextAlignment::Center),
@@ -149,10 +228,19 @@ pub fn play_game(
             }
         },
         RoundTimer,
+        TextRole(ColorRole::Text),
     ));
 
-    let fruit_file_a = format!("icons/{:?}.png", game_settings.fruit_a).to_lowercase();
-    let fruit_file_b = format!("icons/{:?}.png", game_settings.fruit_b).to_lowercase();
+    // Pick this round's fruit pair
+    let shuffled = if game_settings.shuffle {
+        catalogue.random_pair(user.score)
+    } else {
+        None
+    };
+    let (fruit_a, fruit_b) =
+        shuffled.unwrap_or((game_settings.fruit_a.clone(), game_settings.fruit_b.clone()));
+    let fruit_file_a = catalogue.icon(&fruit_a);
+    let fruit_file_b = catalogue.icon(&fruit_b);
 
     for _iter in 3..rng.gen_range(4..20) {
         let shape = choose_fruits(rng.gen_range(1..11));
@@ -160,12 +248,12 @@ pub fn play_game(
         match shape {
             AnswerButton::X => {
                 game_data.exes += 1;
-                game_data.fruit_array.push(game_settings.fruit_a.clone());
+                game_data.fruit_array.push(fruit_a.clone());
                 game_data.file_array.push(fruit_file_a.clone());
             }
             AnswerButton::O => {
                 game_data.os += 1;
-                game_data.fruit_array.push(game_settings.fruit_b.clone());
+                game_data.fruit_array.push(fruit_b.clone());
                 game_data.file_array.push(fruit_file_b.clone());
             }
         }
@@ -178,35 +266,69 @@ pub fn play_game(
         match shape {
             AnswerButton::X => {
                 game_data.exes += 1;
-                game_data.fruit_array.push(game_settings.fruit_a.clone());
+                game_data.fruit_array.push(fruit_a.clone());
                 game_data.file_array.push(fruit_file_a);
             }
 
             AnswerButton::O => {
                 game_data.os += 1;
-                game_data.fruit_array.push(game_settings.fruit_b.clone());
+                game_data.fruit_array.push(fruit_b.clone());
                 game_data.file_array.push(fruit_file_b);
             }
         }
     }
 
+    game_data.fruit_a = fruit_a;
+    game_data.fruit_b = fruit_b;
+
     print_fruits(
-        &asset_server,
+        &assets,
+        &theme,
         &mut commands,
-        &game_data.fruit_array,
-        &game_data.file_array,
+        &game_data,
+        accessibility.large_fruit,
+        layout.portrait,
     );
     next_state.set(AppState::Pause);
 }
 
+// Show this round's fruit pair on the answer buttons
+pub fn update_answer_buttons(
+    assets: Res<GameAssets>,
+    game_data: Res<GameData>,
+    catalogue: Res<FruitCatalogue>,
+    mut button_query: Query<(&AnswerButton, &mut FruitType)>,
+    mut image_query: Query<(&AnswerButton, &mut UiImage), Without<Button>>,
+) {
+    for (answer_button, mut fruit_type) in button_query.iter_mut() {
+        *fruit_type = match answer_button {
+            AnswerButton::X => game_data.fruit_a.clone(),
+            AnswerButton::O => game_data.fruit_b.clone(),
+        };
+    }
+    for (answer_button, mut image) in image_query.iter_mut() {
+        image.texture = match answer_button {
+            AnswerButton::X => assets.fruit_icon(&catalogue.icon(&game_data.fruit_a)),
+            AnswerButton::O => assets.fruit_icon(&catalogue.icon(&game_data.fruit_b)),
+        };
+    }
+}
+
 // Spawn Villagers
 pub fn spawn_chibi_game(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    assets: Res<GameAssets>,
     villagers: Res<Villagers>,
+    layout: Res<ScreenLayout>,
 ) {
     let columns = 10;
     let rows = 1;
+    // Portrait has no room under the fruit, the row shrinks to fit above the level text
+    let (width, height, top) = if layout.portrait {
+        (700.0, 86.0, -330.0)
+    } else {
+        (1024.0, 125.0, 206.0)
+    };
 
     commands
         .spawn((
@@ -214,9 +336,10 @@ pub fn spawn_chibi_game(
                 style: Style {
                     // Use the CSS Grid algorithm for laying out this node
                     display: Display::Grid,
-                    top: Val::Vh(70.0),
-                    width: Val::Vw(30.0),
-                    left: Val::Vw(10.0),
+                    // a row along the bottom of the canvas
+                    justify_self: JustifySelf::Center,
+                    align_self: AlignSelf::Center,
+                    top: Val::Px(top),
                     ..default()
                 },
                 ..default()
@@ -228,11 +351,8 @@ pub fn spawn_chibi_game(
                 .spawn((
                     NodeBundle {
                         style: Style {
-                            height: Val::Px(125.0),
-                            width: Val::Vw(80.0),
-                            // Make the grid have a 1:1 aspect ratio meaning it will scale as an exact square
-                            // As the height is set explicitly, this means the width will adjust to match the height
-                            aspect_ratio: Some(1.0),
+                            height: Val::Px(height),
+                            width: Val::Px(width),
                             // Use grid layout for this node
                             display: Display::Grid,
                             // Set the grid to have 10 columns all with sizes minmax(0, 1fr)
@@ -253,10 +373,11 @@ pub fn spawn_chibi_game(
                     for (index, villager) in villagers.villagers.iter().enumerate() {
                         item_rect_villager(
                             builder,
-                            &asset_server,
+                            &assets,
                             villager.1.clone(),
-                            villager.2.clone(),
+                            villager.2,
                             index % 2 == 0,
+                            Vec2::new(height * 0.8, height),
                         );
                     }
                 });
@@ -264,25 +385,29 @@ pub fn spawn_chibi_game(
 }
 
 fn choose_fruits(random: i32) -> AnswerButton {
-    let return_answer: AnswerButton;
-
     if random % 2 == 0 {
-        return_answer = AnswerButton::X;
+        AnswerButton::X
     } else {
-        return_answer = AnswerButton::O;
+        AnswerButton::O
     }
-
-    return return_answer;
 }
 
 fn print_fruits(
-    asset_server: &Res<AssetServer>,
+    assets: &Res<GameAssets>,
+    theme: &Theme,
     commands: &mut Commands,
-    fruit_array: &Vec<FruitType>,
-    file_array: &Vec<String>,
+    game_data: &GameData,
+    large_fruit: bool,
+    portrait: bool,
 ) {
-    let columns = 10;
-    let rows = 5;
+    // Large fruit trades cells for size, a round never has more than 17 fruit.
+    // Portrait turns the grid on its side below the level text
+    let (columns, rows, width, height, top) = match (large_fruit, portrait) {
+        (false, false) => (10, 5, 828.0, 360.0, 36.0),
+        (true, false) => (6, 3, 994.0, 432.0, 14.0),
+        (false, true) => (5, 10, 360.0, 828.0, 250.0),
+        (true, true) => (3, 6, 396.0, 828.0, 250.0),
+    };
 
     commands
         .spawn((
@@ -290,29 +415,27 @@ fn print_fruits(
                 style: Style {
                     // Use the CSS Grid algorithm for laying out this node
                     display: Display::Grid,
-                    // Make node fill the entirety it's parent (in this case the window)
-                    width: Val::Percent(50.0),
-                    height: Val::Percent(50.0),
-                    // center the node vertically and horizontally within the window
-                    position_type: PositionType::Relative,
-                    top: Val::Percent(30.0),
-                    left: Val::Percent(20.0),
+                    width: Val::Px(width),
+                    height: Val::Px(height),
+                    // center the node vertically and horizontally within the window,
+                    // a little below the level text
+                    justify_self: JustifySelf::Center,
+                    align_self: AlignSelf::Center,
+                    top: Val::Px(top),
                     ..default()
                 },
                 ..default()
             },
-            GridIdentifier::Grid,
+            GridIdentifier::Fruit,
         ))
         .with_children(|builder| {
             builder
                 .spawn((
                     NodeBundle {
                         style: Style {
-                            // Make the height of the node fill its parent
+                            // Make the node fill its parent
+                            width: Val::Percent(100.0),
                             height: Val::Percent(100.0),
-                            // Make the grid have a 1:1 aspect ratio meaning it will scale as an exact square
-                            // As the height is set explicitly, this means the width will adjust to match the height
-                            aspect_ratio: Some(2.3),
                             // Use grid layout for this node
                             display: Display::Grid,
                             // Add 24px of padding around the grid
@@ -331,15 +454,22 @@ fn print_fruits(
                         // background_color: BackgroundColor(Color::BEIGE),
                         ..default()
                     },
-                    GridIdentifier::Grid,
+                    GridIdentifier::Fruit,
                 ))
                 .with_children(|builder| {
-                    for (index, _fruit) in fruit_array.iter().enumerate() {
+                    for (index, fruit) in game_data.fruit_array.iter().enumerate() {
+                        let slot = if *fruit == game_data.fruit_a {
+                            FruitSlot::A
+                        } else {
+                            FruitSlot::B
+                        };
                         item_rect(
                             builder,
-                            asset_server,
-                            file_array[index].clone(),
-                            fruit_array[index].clone(),
+                            assets,
+                            theme,
+                            game_data.file_array[index].clone(),
+                            fruit.clone(),
+                            slot,
                         );
                     }
                 });
@@ -350,199 +480,116 @@ fn process_guess(
     guess: String,
     exes: i32,
     os: i32,
-    asset_server: &Res<AssetServer>,
+    strings: &Strings,
+    theme: &Theme,
     commands: &mut Commands,
     game_data: &mut ResMut<GameData>,
     villagers: &Res<Villagers>,
+    sfx: &mut EventWriter<PlaySfx>,
+    rounds: &mut EventWriter<RoundFinished>,
 ) -> bool {
-    if (guess.trim() == "x" && exes > os) || (guess.trim() == "o" && os > exes) {
+    let reaction = game_data.time_elapsed;
+    game_data.run.answered += 1;
+    game_data.run.reaction_total += reaction;
+    let correct = (guess.trim() == "x" && exes > os) || (guess.trim() == "o" && os > exes);
+    rounds.send(RoundFinished {
+        difference: exes.abs_diff(os),
+        correct,
+        reaction: Some(reaction),
+        fruit_a: game_data.fruit_a.clone(),
+        fruit_b: game_data.fruit_b.clone(),
+    });
+    if correct {
         // Spawn Correct
         game_data.level += 1;
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Correct.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
-        commands.spawn((
-            {
-                TextBundle {
-                    text: Text::from_section(
-                        "Correct!",
-                        TextStyle {
-                            font: asset_server.load(FONT),
-                            font_size: 48.0,
-                            color: OLIVE_GREEN,
-                        },
-                    )
-                    .with_alignment(TextAlignment::Center),
-                    style: Style {
-                        top: Val::Percent(70.0),
-                        left: Val::Percent(43.0),
-
-                        ..default()
-                    },
-                    ..default()
-                }
-            },
-            Animator::new(Tween::new(
-                EaseFunction::CubicOut,
-                Duration::from_millis(500),
-                UiPositionLens {
-                    start: UiRect {
-                        top: Val::Percent(70.0),
-                        left: Val::Percent(43.0),
-
-                        ..default()
-                    },
-                    end: UiRect {
-                        top: Val::Percent(60.0),
-                        left: Val::Percent(43.0),
-
-                        ..default()
-                    },
-                },
-            )),
-        ));
+        game_data.run.correct += 1;
+        sfx.send(PlaySfx(SfxKind::Correct));
+        spawn_result_text(
+            commands,
+            strings.get("round.correct"),
+            theme,
+            ColorRole::Text,
+            (168.0, 96.0),
+        );
         for villager in villagers.villagers.iter() {
             let level_unlockable = villager.3;
             let status = villager.2;
-            if (game_data.level == level_unlockable) && status == false {
-                commands.spawn((
-                    AudioBundle {
-                        source: asset_server.load("music/NewChar.ogg"),
-                        settings: PlaybackSettings {
-                            mode: PlaybackMode::Despawn,
-                            ..Default::default()
-                        },
-                        ..default()
-                    },
-                    SoundEffect,
-                ));
-                commands.spawn((
-                    {
-                        TextBundle {
-                            text: Text::from_section(
-                                "New Villager Unlocked!",
-                                TextStyle {
-                                    font: asset_server.load(FONT),
-                                    font_size: 48.0,
-                                    color: OLIVE_GREEN,
-                                },
-                            )
-                            .with_alignment(TextAlignment::Center),
-                            style: Style {
-                                top: Val::Percent(75.0),
-                                left: Val::Percent(33.0),
-
-                                ..default()
-                            },
-                            ..default()
-                        }
-                    },
-                    Animator::new(Tween::new(
-                        EaseFunction::CubicOut,
-                        Duration::from_millis(500),
-                        UiPositionLens {
-                            start: UiRect {
-                                top: Val::Percent(75.0),
-                                left: Val::Percent(33.0),
-
-                                ..default()
-                            },
-                            end: UiRect {
-                                top: Val::Percent(65.0),
-                                left: Val::Percent(33.0),
-
-                                ..default()
-                            },
-                        },
-                    )),
-                ));
+            if (game_data.level == level_unlockable) && !status {
+                sfx.send(PlaySfx(SfxKind::NewChar));
+                spawn_result_text(
+                    commands,
+                    strings.get("round.new_villager"),
+                    theme,
+                    ColorRole::Text,
+                    (204.0, 132.0),
+                );
             }
         }
         game_data.result = Result::Correct;
-        return true;
+        true
     } else {
         //Spawn Incorrect
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Incorrect.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
+        sfx.send(PlaySfx(SfxKind::Incorrect));
+        spawn_result_text(
+            commands,
+            strings.get("round.incorrect"),
+            theme,
+            ColorRole::Error,
+            (168.0, 96.0),
+        );
+        game_data.result = Result::Incorrect;
+        false
+    }
+}
+
+// Centred text that floats up from `rise.0` to `rise.1` below the middle of the canvas
+fn spawn_result_text(
+    commands: &mut Commands,
+    label: String,
+    theme: &Theme,
+    role: ColorRole,
+    rise: (f32, f32),
+) {
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                label,
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 48.0,
+                    color: role.color(theme),
                 },
+            )
+            .with_alignment(TextAlignment::Center),
+            style: Style {
+                justify_self: JustifySelf::Center,
+                align_self: AlignSelf::Center,
+                top: Val::Px(rise.0),
                 ..default()
             },
-            SoundEffect,
-        ));
-        commands.spawn((
-            {
-                TextBundle {
-                    text: Text::from_section(
-                        "Incorrect!",
-                        TextStyle {
-                            font: asset_server.load(FONT),
-                            font_size: 48.0,
-                            color: Color::CRIMSON,
-                        },
-                    )
-                    .with_alignment(TextAlignment::Center),
-                    style: Style {
-                        top: Val::Percent(70.0),
-                        left: Val::Percent(42.0),
-
-                        ..default()
-                    },
-                    ..default()
-                }
+            ..default()
+        },
+        Animator::new(Tween::new(
+            EaseFunction::CubicOut,
+            Duration::from_millis(500),
+            UiPositionLens {
+                start: UiRect::top(Val::Px(rise.0)),
+                end: UiRect::top(Val::Px(rise.1)),
             },
-            Animator::new(Tween::new(
-                EaseFunction::CubicOut,
-                Duration::from_millis(500),
-                UiPositionLens {
-                    start: UiRect {
-                        top: Val::Percent(70.0),
-                        left: Val::Percent(42.0),
-
-                        ..default()
-                    },
-                    end: UiRect {
-                        top: Val::Percent(60.0),
-                        left: Val::Percent(42.0),
-
-                        ..default()
-                    },
-                },
-            )),
-        ));
-        game_data.result = Result::Incorrect;
-        return false;
-    }
+        )),
+        TextRole(role),
+    ));
 }
 
 pub fn setup_ui(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
+    assets: &Res<GameAssets>,
+    theme: &Theme,
     game_settings: &Res<GameSettings>,
+    catalogue: &Res<FruitCatalogue>,
 ) {
-    let fruit_file_a = format!("icons/{:?}.png", game_settings.fruit_a).to_lowercase();
-    let fruit_file_b = format!("icons/{:?}.png", game_settings.fruit_b).to_lowercase();
-
-    // Spawn Music
-    commands.spawn(AudioBundle {
-        source: asset_server.load("music/Bees.ogg"),
-        settings: PlaybackSettings {
-            volume: Volume::new_relative(0.5),
-            ..Default::default()
-        },
-        ..default()
-    });
+    let fruit_file_a = catalogue.icon(&game_settings.fruit_a);
+    let fruit_file_b = catalogue.icon(&game_settings.fruit_b);
 
     commands
         .spawn((NodeBundle {
@@ -557,35 +604,15 @@ pub fn setup_ui(
             ..default()
         },))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(150.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    game_settings.fruit_a.clone(),
-                    AnswerButton::X,
-                ))
+            ButtonBuilder::empty((game_settings.fruit_a.clone(), AnswerButton::X))
+                .border(ButtonBorder::Accent)
+                .click(SfxKind::Answer)
+                .spawn(parent, theme)
                 .with_children(|parent| {
                     parent.spawn((
                         ImageBundle {
                             image: UiImage {
-                                texture: asset_server.load(fruit_file_a),
+                                texture: assets.fruit_icon(&fruit_file_a),
                                 ..default()
                             },
                             ..default()
@@ -593,6 +620,7 @@ pub fn setup_ui(
                         game_settings.fruit_a.clone(),
                         AnswerButton::X,
                     ));
+                    spawn_fruit_mark(parent, theme, Some(FruitSlot::A));
                 });
         });
 
@@ -609,35 +637,15 @@ pub fn setup_ui(
             ..default()
         },))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(150.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    game_settings.fruit_b.clone(),
-                    AnswerButton::O,
-                ))
+            ButtonBuilder::empty((game_settings.fruit_b.clone(), AnswerButton::O))
+                .border(ButtonBorder::Accent)
+                .click(SfxKind::Answer)
+                .spawn(parent, theme)
                 .with_children(|parent| {
                     parent.spawn((
                         ImageBundle {
                             image: UiImage {
-                                texture: asset_server.load(fruit_file_b),
+                                texture: assets.fruit_icon(&fruit_file_b),
                                 ..default()
                             },
                             ..default()
@@ -645,129 +653,89 @@ pub fn setup_ui(
                         game_settings.fruit_b.clone(),
                         AnswerButton::O,
                     ));
+                    spawn_fruit_mark(parent, theme, Some(FruitSlot::B));
                 });
         });
 }
 
 pub fn interact_button(
-    mut interaction_query: Query<
-        (&Interaction, &AnswerButton, &mut BorderColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    interaction_query: Query<(&Interaction, &AnswerButton), (Changed<Interaction>, With<Button>)>,
     mut game_data: ResMut<GameData>,
     mut next_state: ResMut<NextState<AppState>>,
-    asset_server: Res<AssetServer>,
+    strings: Strings,
+    theme: Res<Theme>,
     mut commands: Commands,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Actions,
     mut timer: ResMut<PauseTimer>,
     villagers: Res<Villagers>,
+    mut sfx: EventWriter<PlaySfx>,
+    mut rounds: EventWriter<RoundFinished>,
 ) {
     // Keyboard Input
-    // Escape to Main Menu
-    if keyboard_input.just_released(KeyCode::Escape) {
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Back.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
-        next_state.set(AppState::GameOver);
+    // Escape to the pause menu
+    if actions.just_released(Action::Back) || actions.just_released(Action::Pause) {
+        sfx.send(PlaySfx(SfxKind::Back));
+        next_state.set(AppState::PauseMenu);
     }
     // Fruit A Select
-    if keyboard_input.just_released(KeyCode::Z) {
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Answer.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
+    if actions.just_released(Action::AnswerA) {
+        sfx.send(PlaySfx(SfxKind::Answer));
         game_data.player_guess = "x".to_string();
         timer.pause_timer.pause();
         process_guess(
             game_data.player_guess.clone(),
             game_data.exes,
             game_data.os,
-            &asset_server,
+            &strings,
+            &theme,
             &mut commands,
             &mut game_data,
             &villagers,
+            &mut sfx,
+            &mut rounds,
         );
         next_state.set(AppState::ShowResults);
     }
     // Fruit B Select
-    if keyboard_input.just_released(KeyCode::X) {
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Answer.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
+    if actions.just_released(Action::AnswerB) {
+        sfx.send(PlaySfx(SfxKind::Answer));
         game_data.player_guess = "o".to_string();
         timer.pause_timer.pause();
         process_guess(
             game_data.player_guess.clone(),
             game_data.exes,
             game_data.os,
-            &asset_server,
+            &strings,
+            &theme,
             &mut commands,
             &mut game_data,
             &villagers,
+            &mut sfx,
+            &mut rounds,
         );
         next_state.set(AppState::ShowResults);
     }
     // UI Button Input
-    for (interaction, answer_button, mut border_color) in interaction_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.spawn((
-                    AudioBundle {
-                        source: asset_server.load("music/Answer.ogg"),
-                        settings: PlaybackSettings {
-                            mode: PlaybackMode::Despawn,
-                            ..Default::default()
-                        },
-                        ..default()
-                    },
-                    SoundEffect,
-                ));
-                *border_color = Color::WHITE.into();
-                match answer_button {
-                    AnswerButton::X => game_data.player_guess = "x".to_string(),
-                    AnswerButton::O => game_data.player_guess = "o".to_string(),
-                }
-                timer.pause_timer.pause();
-                process_guess(
-                    game_data.player_guess.clone(),
-                    game_data.exes,
-                    game_data.os,
-                    &asset_server,
-                    &mut commands,
-                    &mut game_data,
-                    &villagers,
-                );
-                next_state.set(AppState::ShowResults);
-            }
-            Interaction::Hovered => {
-                *border_color = Color::SALMON.into();
-            }
-            Interaction::None => {
-                *border_color = BASIL_GREEN.into();
+    for (interaction, answer_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match answer_button {
+                AnswerButton::X => game_data.player_guess = "x".to_string(),
+                AnswerButton::O => game_data.player_guess = "o".to_string(),
             }
+            timer.pause_timer.pause();
+            process_guess(
+                game_data.player_guess.clone(),
+                game_data.exes,
+                game_data.os,
+                &strings,
+                &theme,
+                &mut commands,
+                &mut game_data,
+                &villagers,
+                &mut sfx,
+                &mut rounds,
+            );
+            next_state.set(AppState::ShowResults);
         }
     }
 }
@@ -776,47 +744,161 @@ pub fn show_results(
     time: Res<Time>,
     mut timer: ResMut<ResultTimer>,
     mut next_state: ResMut<NextState<AppState>>,
-    game_data: ResMut<GameData>,
+    mut game_data: ResMut<GameData>,
     mut villagers: ResMut<Villagers>,
 ) {
-    timer.result_timer.tick(time.delta());
-    if timer.result_timer.finished() {
+    let timer = &mut *timer;
+    let result_timer = match game_data.result {
+        Result::Correct => &mut timer.result_timer,
+        Result::Incorrect | Result::TimedOut => &mut timer.reveal_timer,
+    };
+    result_timer.tick(time.delta());
+    if result_timer.finished() {
+        result_timer.reset();
         match game_data.result {
             Result::Correct => {
                 for villager in villagers.villagers.iter_mut() {
                     let level_unlockable = villager.3;
                     if game_data.level == level_unlockable {
+                        if !villager.2 {
+                            game_data.run.villagers_unlocked += 1;
+                        }
                         villager.2 = true;
                     }
                 }
                 next_state.set(AppState::StartRound);
-                timer.result_timer.reset();
             }
-            Result::Incorrect => {
+            Result::Incorrect | Result::TimedOut => {
                 next_state.set(AppState::GameOver);
-                timer.result_timer.reset();
             }
         }
     }
 }
 
+// After a miss, show how many of each fruit there were and set the majority bouncing
+pub fn reveal_answer(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    catalogue: Res<FruitCatalogue>,
+    strings: Strings,
+    theme: Res<Theme>,
+    mut fruit_query: Query<(Entity, &FruitType, &mut BackgroundColor), With<GridIdentifier>>,
+) {
+    if game_data.result == Result::Correct {
+        return;
+    }
+    let majority = if game_data.exes > game_data.os {
+        &game_data.fruit_a
+    } else {
+        &game_data.fruit_b
+    };
+
+    for (entity, fruit_type, mut tint) in fruit_query.iter_mut() {
+        if fruit_type == majority {
+            commands.entity(entity).insert(Animator::new(
+                Tween::new(
+                    EaseFunction::QuadraticInOut,
+                    Duration::from_millis(300),
+                    TransformScaleLens {
+                        start: Vec3::ONE,
+                        end: Vec3::splat(1.25),
+                    },
+                )
+                .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
+                .with_repeat_count(RepeatCount::Infinite),
+            ));
+        } else {
+            tint.0 = Color::WHITE.with_a(0.35);
+        }
+    }
+
+    // Spawn Fruit Counts
+    let role = |fruit_type: &FruitType| {
+        if fruit_type == majority {
+            ColorRole::Emphasis
+        } else {
+            ColorRole::Text
+        }
+    };
+    let count = |fruit_type: &FruitType, count: i32| TextSection {
+        value: strings.format(
+            "round.count",
+            &[
+                ("fruit", strings.fruit_name(&catalogue, fruit_type)),
+                ("count", count.to_string()),
+            ],
+        ),
+        style: TextStyle {
+            font: theme.font.clone(),
+            font_size: 40.0,
+            color: role(fruit_type).color(&theme),
+        },
+    };
+    commands.spawn((
+        TextBundle {
+            text: Text::from_sections([
+                count(&game_data.fruit_a, game_data.exes),
+                TextSection::new(
+                    format!("  {}  ", strings.get("round.versus")),
+                    theme.text_style(40.0),
+                ),
+                count(&game_data.fruit_b, game_data.os),
+            ])
+            .with_alignment(TextAlignment::Center),
+            style: Style {
+                justify_self: JustifySelf::Center,
+                align_self: AlignSelf::Center,
+                top: Val::Px(160.0),
+                ..default()
+            },
+            ..default()
+        },
+        SectionRoles(vec![
+            role(&game_data.fruit_a),
+            ColorRole::Text,
+            role(&game_data.fruit_b),
+        ]),
+    ));
+}
+
 pub fn tick_elapsed(mut game_data: ResMut<GameData>, time: Res<Time>) {
     game_data.time_elapsed += time.delta();
 }
 
 pub fn pause(
+    mut commands: Commands,
     time: Res<Time>,
     mut timer: ResMut<PauseTimer>,
     mut next_state: ResMut<NextState<AppState>>,
     mut game_data: ResMut<GameData>,
+    strings: Strings,
+    theme: Res<Theme>,
+    mut sfx: EventWriter<PlaySfx>,
+    mut rounds: EventWriter<RoundFinished>,
 ) {
     timer.pause_timer.tick(time.delta());
 
-    if timer.pause_timer.finished() {
-        if game_data.player_guess.is_empty() {
-            next_state.set(AppState::GameOver);
-            timer.pause_timer.reset();
-        }
+    if timer.pause_timer.finished() && game_data.player_guess.is_empty() {
+        // Spawn Timed Out
+        sfx.send(PlaySfx(SfxKind::Incorrect));
+        spawn_result_text(
+            &mut commands,
+            strings.get("round.timed_out"),
+            &theme,
+            ColorRole::Error,
+            (168.0, 96.0),
+        );
+        game_data.run.timed_out += 1;
+        rounds.send(RoundFinished {
+            difference: game_data.exes.abs_diff(game_data.os),
+            correct: false,
+            reaction: None,
+            fruit_a: game_data.fruit_a.clone(),
+            fruit_b: game_data.fruit_b.clone(),
+        });
+        game_data.result = Result::TimedOut;
+        next_state.set(AppState::ShowResults);
+        timer.pause_timer.reset();
     }
     if timer.pause_timer.paused() {
         timer.pause_timer.unpause();
@@ -833,7 +915,8 @@ pub fn upload_score(game_data: ResMut<GameData>, mut round_event: EventWriter<Up
 
 pub fn clear_shapes(
     mut commands: Commands,
-    mut query_text: Query<Entity, (With<Text>, Without<AnswerButton>)>,
+    // Marks on the answer buttons outlive the round
+    mut query_text: Query<Entity, (With<Text>, Without<AnswerButton>, Without<MarkLetter>)>,
     mut query_fruit: Query<Entity, (With<FruitType>, Without<Node>)>,
     mut query_grid: Query<Entity, With<GridIdentifier>>,
 ) {
@@ -861,10 +944,23 @@ pub fn game_over(
     mut query_text: Query<Entity, With<Text>>,
     mut query_button: Query<Entity, With<Style>>,
     mut query_fruit: Query<Entity, With<FruitType>>,
-    mut query_music: Query<Entity, (With<PlaybackSettings>, Without<SoundEffect>)>,
     mut next_state: ResMut<NextState<AppState>>,
     mut game_data: ResMut<GameData>,
+    user: Res<User>,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
+    game_settings: Res<GameSettings>,
+    catalogue: Res<FruitCatalogue>,
 ) {
+    // upload_score has only just sent this run's score, the best is still the old one
+    let score = (game_data.level - 1) as i64;
+    game_data.run.level = game_data.level;
+    game_data.run.score = score;
+    game_data.run.new_best = score > 0 && score > user.score;
+    game_data.run.ending = match game_data.result {
+        Result::Correct => None,
+        result => Some(result),
+    };
     game_data.level = 1;
     for entity in query_text.iter_mut() {
         if let Some(entity) = commands.get_entity(entity) {
@@ -882,19 +978,25 @@ pub fn game_over(
             entity.despawn_recursive();
         }
     }
-    for entity in query_music.iter_mut() {
-        if let Some(entity) = commands.get_entity(entity) {
-            entity.despawn_recursive();
+
+    match std::mem::take(&mut game_data.exit) {
+        RunExit::Results => next_state.set(AppState::Results),
+        RunExit::Restart => {
+            // The answer buttons went with everything else
+            setup_ui(&mut commands, &assets, &theme, &game_settings, &catalogue);
+            next_state.set(AppState::StartRound);
         }
+        RunExit::MainMenu => next_state.set(AppState::MainMenu),
     }
-    next_state.set(AppState::MainMenu);
 }
 
 fn item_rect(
     builder: &mut ChildBuilder,
-    asset_server: &Res<AssetServer>,
+    assets: &Res<GameAssets>,
+    theme: &Theme,
     fruit_file: String,
     fruit_type: FruitType,
+    slot: FruitSlot,
 ) {
     builder
         .spawn((
@@ -906,16 +1008,16 @@ fn item_rect(
                 },
                 ..default()
             },
-            GridIdentifier::Grid,
+            GridIdentifier::Fruit,
         ))
         .with_children(|builder| {
             builder.spawn((
                 ImageBundle {
-                    image: asset_server.load(fruit_file).into(),
+                    image: assets.fruit_icon(&fruit_file).into(),
                     ..default()
                 },
                 fruit_type.clone(),
-                GridIdentifier::Grid,
+                GridIdentifier::Fruit,
                 Animator::new(
                     Tween::new(
                         EaseFunction::CubicInOut,
@@ -929,15 +1031,17 @@ fn item_rect(
                     .with_repeat_count(RepeatCount::Infinite),
                 ),
             ));
+            spawn_fruit_mark(builder, theme, Some(slot));
         });
 }
 
 fn item_rect_villager(
     builder: &mut ChildBuilder,
-    asset_server: &Res<AssetServer>,
+    assets: &Res<GameAssets>,
     char_file: String,
     unlocked: bool,
     bounce_type: bool,
+    size: Vec2,
 ) {
     builder
         .spawn((
@@ -956,11 +1060,11 @@ fn item_rect_villager(
             builder.spawn((
                 ImageBundle {
                     style: Style {
-                        height: Val::Px(125.0),
-                        width: Val::Px(100.0),
+                        height: Val::Px(size.y),
+                        width: Val::Px(size.x),
                         ..default()
                     },
-                    image: asset_server.load(char_file).into(),
+                    image: assets.character(&char_file).into(),
                     background_color: (if unlocked {
                         Color::WHITE.into()
                     } else {
@@ -1,10 +1,10 @@
-use bevy::{
-    audio::{PlaybackMode, Volume},
-    prelude::*,
-};
+use bevy::prelude::*;
 
 use crate::{
-    main_menu::{SoundEffect, FONT, OLIVE_GREEN},
+    audio::{PlaySfx, SfxKind},
+    input::{Action, Actions, InputMap},
+    locale::Strings,
+    ui::{spawn_button, ColorRole, TextRole, Theme},
     AppState,
 };
 
@@ -13,50 +13,44 @@ pub enum BackButton {
     MainMenu,
 }
 
-pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // Spawn Music
-    commands.spawn(AudioBundle {
-        source: asset_server.load("music/Path.ogg"),
-        settings: PlaybackSettings {
-            volume: Volume::new_relative(0.5),
-            ..Default::default()
-        },
-        ..default()
-    });
+pub fn setup_ui(
+    mut commands: Commands,
+    strings: Strings,
+    theme: Res<Theme>,
+    input_map: Res<InputMap>,
+) {
     // Spawn Title Text
-    let title = format!("How To Play");
+    let title = strings.get("how_to_play.title");
 
-    commands.spawn({
+    commands.spawn((
         TextBundle {
             text: Text::from_section(
                 title,
                 TextStyle {
-                    font: asset_server.load(FONT),
+                    font: theme.font.clone(),
                     font_size: 64.0,
-                    color: OLIVE_GREEN,
+                    color: theme.text,
                 },
             )
             .with_alignment(TextAlignment::Center),
             style: Style {
-                top: Val::Vh(-30.0),
+                top: Val::Px(-216.0),
                 justify_self: JustifySelf::Center,
                 align_self: AlignSelf::Center,
                 ..default()
             },
             ..default()
-        }
-    });
+        },
+        TextRole(ColorRole::Text),
+    ));
 
-    let subtitle = format!(
-        "The village of Odemay is hungry! They need more fruit to survive the winter. 
-    Could you lend a hand in picking the fruit that is more abundant?"
-    );
-    let text = format!(
-        "    .:. Two types of fruit will spawn on the screen in different quantities .:.
-    .:. Choose which fruit there is more of using the Z and X keys or the on screen buttons .:.
-    .:. Each level gives you five seconds to guess. Guess as quickly as you can for a better score! .:.
-    .:. If you guess incorrectly, the game is over and you go back to the main menu .:.
-    .:. Depending on your performance, you can unlock more characters for your village .:."
+    let subtitle = strings.get("how_to_play.story");
+    let text = strings.format(
+        "how_to_play.rules",
+        &[
+            ("fruit_a", input_map.key_name(Action::AnswerA)),
+            ("fruit_b", input_map.key_name(Action::AnswerB)),
+        ],
     );
 
     commands
@@ -74,14 +68,14 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
         .with_children(|parent| {
             parent
                 // Spawn Subtitle Text
-                .spawn({
+                .spawn((
                     TextBundle {
                         text: Text::from_section(
                             subtitle,
                             TextStyle {
-                                font: asset_server.load(FONT),
+                                font: theme.font.clone(),
                                 font_size: 32.0,
-                                color: Color::SALMON,
+                                color: theme.emphasis,
                             },
                         )
                         .with_alignment(TextAlignment::Center),
@@ -92,17 +86,18 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ..default()
                         },
                         ..default()
-                    }
-                });
+                    },
+                    TextRole(ColorRole::Emphasis),
+                ));
             // Spawn Text
-            parent.spawn({
+            parent.spawn((
                 TextBundle {
                     text: Text::from_section(
                         text,
                         TextStyle {
-                            font: asset_server.load(FONT),
+                            font: theme.font.clone(),
                             font_size: 27.0,
-                            color: OLIVE_GREEN,
+                            color: theme.text,
                         },
                     )
                     .with_alignment(TextAlignment::Center),
@@ -110,14 +105,15 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                         justify_self: JustifySelf::Center,
                         align_self: AlignSelf::Center,
                         margin: UiRect {
-                            top: Val::Vh(5.0),
+                            top: Val::Px(36.0),
                             ..default()
                         },
                         ..default()
                     },
                     ..default()
-                }
-            });
+                },
+                TextRole(ColorRole::Text),
+            ));
         });
 
     // Spawn Menu Button
@@ -136,94 +132,30 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
             BackButton::MainMenu,
         ))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(150.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    BackButton::MainMenu,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        TextBundle::from_section(
-                            "Back",
-                            TextStyle {
-                                font: asset_server.load("fonts/Leila-Regular.ttf"),
-                                font_size: 40.0,
-                                color: OLIVE_GREEN,
-                            },
-                        ),
-                        BackButton::MainMenu,
-                    ));
-                });
+            spawn_button(
+                parent,
+                &theme,
+                strings.get("common.back"),
+                BackButton::MainMenu,
+            );
         });
 }
 
 pub fn interact_button(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut interaction_query: Query<
-        (&Interaction, &BackButton, &mut BorderColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    interaction_query: Query<(&Interaction, &BackButton), (Changed<Interaction>, With<Button>)>,
     mut next_state: ResMut<NextState<AppState>>,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Actions,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
-    if keyboard_input.just_released(KeyCode::Escape) {
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Back.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
+    if actions.just_released(Action::Back) {
+        sfx.send(PlaySfx(SfxKind::Back));
         next_state.set(AppState::MainMenu);
     }
     // Buttons
-    for (interaction, answer_button, mut border_color) in interaction_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.spawn((
-                    AudioBundle {
-                        source: asset_server.load("music/Select.ogg"),
-                        settings: PlaybackSettings {
-                            mode: PlaybackMode::Despawn,
-                            ..Default::default()
-                        },
-                        ..default()
-                    },
-                    SoundEffect,
-                ));
-                *border_color = Color::WHITE.into();
-                match answer_button {
-                    BackButton::MainMenu => next_state.set(AppState::MainMenu),
-                }
-            }
-            Interaction::Hovered => {
-                *border_color = Color::SALMON.into();
-            }
-            Interaction::None => {
-                *border_color = OLIVE_GREEN.into();
+    for (interaction, back_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match back_button {
+                BackButton::MainMenu => next_state.set(AppState::MainMenu),
             }
         }
     }
@@ -233,7 +165,6 @@ pub fn clear_shapes(
     mut commands: Commands,
     mut query: Query<Entity, With<BackButton>>,
     mut query_title: Query<Entity, With<Text>>,
-    mut query_music: Query<Entity, (With<PlaybackSettings>, Without<SoundEffect>)>,
 ) {
     for entity in query.iter_mut() {
         if let Some(entity) = commands.get_entity(entity) {
@@ -245,9 +176,4 @@ pub fn clear_shapes(
             entity.despawn_recursive();
         }
     }
-    for entity in query_music.iter_mut() {
-        if let Some(entity) = commands.get_entity(entity) {
-            entity.despawn_recursive();
-        }
-    }
 }
@@ -0,0 +1,242 @@
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const INPUT_MAP_SAVE: &str = "controls";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    AnswerA,
+    AnswerB,
+    Back,
+    Confirm,
+    Pause,
+    Mute,
+    Swap,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::AnswerA,
+        Action::AnswerB,
+        Action::Back,
+        Action::Confirm,
+        Action::Pause,
+        Action::Mute,
+        Action::Swap,
+    ];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Action::AnswerA => "action.answer_a",
+            Action::AnswerB => "action.answer_b",
+            Action::Back => "action.back",
+            Action::Confirm => "action.confirm",
+            Action::Pause => "action.pause",
+            Action::Mute => "action.mute",
+            Action::Swap => "action.swap",
+        }
+    }
+}
+
+// Keys that can be bound to an action and written to the save
+const BINDABLE_KEYS: [KeyCode; 52] = [
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Space,
+    KeyCode::Return,
+    KeyCode::Escape,
+    KeyCode::Tab,
+    KeyCode::Back,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+];
+
+pub fn bindable_keys() -> impl Iterator<Item = KeyCode> {
+    BINDABLE_KEYS.into_iter()
+}
+
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Return => "Enter".to_string(),
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::Back => "Backspace".to_string(),
+        _ => {
+            let name = format!("{:?}", key);
+            match name.strip_prefix("Key") {
+                Some(digit) => digit.to_string(),
+                None => name,
+            }
+        }
+    }
+}
+
+fn key_from_name(name: &str) -> Option<KeyCode> {
+    bindable_keys().find(|key| format!("{:?}", key) == name)
+}
+
+#[derive(Resource, Clone)]
+pub struct InputMap {
+    pub keys: HashMap<Action, KeyCode>,
+    pub buttons: HashMap<Action, GamepadButtonType>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self {
+            keys: HashMap::from([
+                (Action::AnswerA, KeyCode::Z),
+                (Action::AnswerB, KeyCode::X),
+                (Action::Back, KeyCode::Escape),
+                (Action::Confirm, KeyCode::Return),
+                (Action::Pause, KeyCode::P),
+                (Action::Mute, KeyCode::M),
+                (Action::Swap, KeyCode::S),
+            ]),
+            // Left and right face buttons line up with the on screen answers
+            buttons: HashMap::from([
+                (Action::AnswerA, GamepadButtonType::West),
+                (Action::AnswerB, GamepadButtonType::East),
+                (Action::Back, GamepadButtonType::Select),
+                (Action::Confirm, GamepadButtonType::South),
+                (Action::Pause, GamepadButtonType::Start),
+                (Action::Mute, GamepadButtonType::North),
+                (Action::Swap, GamepadButtonType::RightTrigger),
+            ]),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedInputMap {
+    bindings: Vec<(Action, String)>,
+}
+
+impl InputMap {
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| InputMap::default().keys[&action])
+    }
+
+    pub fn button(&self, action: Action) -> GamepadButtonType {
+        self.buttons
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| InputMap::default().buttons[&action])
+    }
+
+    pub fn key_name(&self, action: Action) -> String {
+        key_name(self.key(action))
+    }
+
+    /// Binds a key to an action, handing the old key to whichever action held the new one
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        let previous = self.key(action);
+        for (other, other_key) in self.keys.iter_mut() {
+            if *other != action && *other_key == key {
+                *other_key = previous;
+            }
+        }
+        self.keys.insert(action, key);
+    }
+
+    pub fn save(&self) {
+        let bindings = Action::ALL
+            .iter()
+            .map(|action| (*action, format!("{:?}", self.key(*action))))
+            .collect();
+        storage::save(INPUT_MAP_SAVE, &SavedInputMap { bindings });
+    }
+}
+
+pub fn load_input_map(mut input_map: ResMut<InputMap>) {
+    if let Some(saved) = storage::load::<SavedInputMap>(INPUT_MAP_SAVE) {
+        for (action, name) in saved.bindings {
+            if let Some(key) = key_from_name(&name) {
+                input_map.bind(action, key);
+            }
+        }
+    }
+}
+
+/// Reads actions through the player's key bindings and any connected gamepad
+#[derive(SystemParam)]
+pub struct Actions<'w> {
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    gamepad_input: Res<'w, Input<GamepadButton>>,
+    gamepads: Res<'w, Gamepads>,
+    input_map: Res<'w, InputMap>,
+}
+
+impl Actions<'_> {
+    pub fn just_released(&self, action: Action) -> bool {
+        self.key_just_released(action) || self.gamepad_just_released(action)
+    }
+
+    pub fn key_just_released(&self, action: Action) -> bool {
+        self.keyboard_input
+            .just_released(self.input_map.key(action))
+    }
+
+    pub fn gamepad_just_pressed(&self, action: Action) -> bool {
+        let button_type = self.input_map.button(action);
+        self.gamepads.iter().any(|gamepad| {
+            self.gamepad_input
+                .just_pressed(GamepadButton::new(gamepad, button_type))
+        })
+    }
+
+    pub fn gamepad_just_released(&self, action: Action) -> bool {
+        let button_type = self.input_map.button(action);
+        self.gamepads.iter().any(|gamepad| {
+            self.gamepad_input
+                .just_released(GamepadButton::new(gamepad, button_type))
+        })
+    }
+}
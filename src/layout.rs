@@ -0,0 +1,115 @@
+use bevy::{prelude::*, render::camera::ScalingMode, window::PrimaryWindow};
+
+use crate::ui::Scenery;
+
+// Every screen is laid out for this canvas, in world units for the camera and
+// in `Val::Px` for the UI, and scaled to fit whatever window it ends up in
+pub const CANVAS_WIDTH: f32 = 1280.0;
+pub const CANVAS_HEIGHT: f32 = 720.0;
+// Portrait screens lay the UI out on the canvas turned on its side, so it
+// keeps a readable size on phones instead of shrinking to fit 1280 across
+pub const PORTRAIT_CANVAS_WIDTH: f32 = 720.0;
+pub const PORTRAIT_CANVAS_HEIGHT: f32 = 1280.0;
+
+/// The whole canvas stays in view and keeps its shape, wider or taller
+/// windows get extra room around it instead of stretching it
+pub fn canvas_scaling() -> ScalingMode {
+    ScalingMode::AutoMin {
+        min_width: CANVAS_WIDTH,
+        min_height: CANVAS_HEIGHT,
+    }
+}
+
+#[derive(Resource, Default, PartialEq)]
+pub struct ScreenLayout {
+    pub portrait: bool,
+}
+
+/// Node that is restyled on portrait screens, and put back as it was in landscape
+#[derive(Component)]
+pub struct PortraitStyle {
+    restyle: fn(&mut Style),
+    landscape: Option<Style>,
+}
+
+impl PortraitStyle {
+    pub fn new(restyle: fn(&mut Style)) -> Self {
+        Self {
+            restyle,
+            landscape: None,
+        }
+    }
+}
+
+/// Rows too wide for the portrait canvas stack into a centred column
+pub fn stack_in_column(style: &mut Style) {
+    style.flex_direction = FlexDirection::Column;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.row_gap = Val::Px(16.);
+}
+
+pub fn update_layout(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut layout: ResMut<ScreenLayout>,
+    mut ui_scale: ResMut<UiScale>,
+    mut scenery_query: Query<&mut Transform, With<Scenery>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let (width, height) = (window.width(), window.height());
+    if width <= 0.0 || height <= 0.0 {
+        return;
+    }
+    layout.set_if_neq(ScreenLayout {
+        portrait: height > width,
+    });
+
+    // The UI fits the canvas the same way the camera does
+    let scale = if layout.portrait {
+        (width / PORTRAIT_CANVAS_WIDTH).min(height / PORTRAIT_CANVAS_HEIGHT)
+    } else {
+        (width / CANVAS_WIDTH).min(height / CANVAS_HEIGHT)
+    } as f64;
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+
+    // Grow the scenery past the canvas so that extra room isn't left bare
+    let visible = if width / height > CANVAS_WIDTH / CANVAS_HEIGHT {
+        Vec2::new(CANVAS_HEIGHT * width / height, CANVAS_HEIGHT)
+    } else {
+        Vec2::new(CANVAS_WIDTH, CANVAS_WIDTH * height / width)
+    };
+    let cover = (visible.x / CANVAS_WIDTH).max(visible.y / CANVAS_HEIGHT);
+    for mut transform in scenery_query.iter_mut() {
+        if transform.scale.x != cover {
+            transform.scale = Vec3::new(cover, cover, 1.0);
+        }
+    }
+}
+
+pub fn apply_portrait_styles(
+    layout: Res<ScreenLayout>,
+    mut query: Query<(&mut PortraitStyle, &mut Style)>,
+    added_query: Query<(), Added<PortraitStyle>>,
+) {
+    if !layout.is_changed() && added_query.is_empty() {
+        return;
+    }
+    for (mut portrait_style, mut style) in query.iter_mut() {
+        match (layout.portrait, portrait_style.landscape.is_some()) {
+            (true, false) => {
+                portrait_style.landscape = Some(style.clone());
+                (portrait_style.restyle)(&mut style);
+            }
+            (false, true) => {
+                if let Some(landscape) = portrait_style.landscape.take() {
+                    *style = landscape;
+                }
+            }
+            _ => (),
+        }
+    }
+}
@@ -1,11 +1,13 @@
-use bevy::{
-    audio::{PlaybackMode, Volume},
-    prelude::*,
-};
+use bevy::prelude::*;
 
 use crate::{
-    main_menu::{SoundEffect, BASIL_GREEN, FONT, OLIVE_GREEN},
+    audio::{PlaySfx, SfxKind},
+    focus,
+    input::{Action, Actions},
+    locale::Strings,
+    options::Rebinding,
     settings::Villagers,
+    ui::{spawn_button, ColorRole, FillRole, TextRole, Theme},
     AppState,
 };
 
@@ -37,6 +39,7 @@ impl Plugin for LeaderboardPlugin {
             ),
         )
         .init_resource::<User>()
+        .init_resource::<ScoreUpload>()
         .add_event::<UpdateRoundEvent>();
     }
 }
@@ -46,6 +49,12 @@ pub struct UpdateRoundEvent {
     pub round: i64,
 }
 
+/// Whether a score is still on its way to the server
+#[derive(Resource, Default)]
+pub struct ScoreUpload {
+    pub in_flight: bool,
+}
+
 #[derive(Resource, Default, Deserialize, Serialize)]
 pub struct User {
     pub user: String,
@@ -88,10 +97,11 @@ struct UserScore {
 pub fn setup_scene(
     mut commands: Commands,
     login_data: ResMut<User>,
-    asset_server: Res<AssetServer>,
+    strings: Strings,
+    theme: Res<Theme>,
 ) {
     if !login_data.user.is_empty() {
-        spawn_user_text(&mut commands, &login_data, &asset_server);
+        spawn_user_text(&mut commands, &login_data, &strings, &theme);
 
         return;
     }
@@ -112,18 +122,20 @@ pub fn setup_scene(
             LoginForm,
         ))
         .with_children(|builder| {
-            builder.spawn(TextBundle {
-                text: Text::from_section(
-                    "USERNAME : min 3".to_string(),
-                    TextStyle {
-                        font: asset_server.load(FONT),
-                        font_size: 20.0,
-                        color: OLIVE_GREEN,
-                        ..default()
-                    },
-                ),
-                ..default()
-            });
+            builder.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        strings.get("leaderboard.username"),
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 20.0,
+                            color: theme.text,
+                        },
+                    ),
+                    ..default()
+                },
+                TextRole(ColorRole::Text),
+            ));
             builder
                 .spawn((
                     ButtonBundle {
@@ -136,38 +148,40 @@ pub fn setup_scene(
                     },
                     Username,
                     Editable,
+                    FillRole(ColorRole::Text),
                 ))
                 .with_children(|builder| {
                     builder.spawn(TextBundle {
                         text: Text::from_section(
                             "".to_string(),
                             TextStyle {
-                                font: asset_server.load(FONT),
+                                font: theme.font.clone(),
                                 font_size: 40.0,
                                 color: Color::BEIGE,
-                                ..default()
                             },
                         ),
                         ..default()
                     });
                 });
 
-            builder.spawn(TextBundle {
-                text: Text::from_section(
-                    "PIN : only digits, min 4".to_string(),
-                    TextStyle {
-                        font: asset_server.load(FONT),
-                        font_size: 20.0,
-                        color: OLIVE_GREEN,
+            builder.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        strings.get("leaderboard.pin"),
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 20.0,
+                            color: theme.text,
+                        },
+                    ),
+                    style: Style {
+                        margin: UiRect::top(Val::Px(20.0)),
                         ..default()
                     },
-                ),
-                style: Style {
-                    margin: UiRect::top(Val::Px(20.0)),
                     ..default()
                 },
-                ..default()
-            });
+                TextRole(ColorRole::Text),
+            ));
             builder
                 .spawn((
                     ButtonBundle {
@@ -182,13 +196,14 @@ pub fn setup_scene(
                         value: String::new(),
                     },
                     Editable,
+                    FillRole(ColorRole::Text),
                 ))
                 .with_children(|builder| {
                     builder.spawn(TextBundle {
                         text: Text::from_section(
                             "".to_string(),
                             TextStyle {
-                                // font: asset_server.load(FONT),
+                                // font: assets.font.clone(),
                                 font_size: 40.0,
                                 color: Color::BEIGE,
                                 ..default()
@@ -215,16 +230,16 @@ pub fn setup_scene(
                         ..default()
                     },
                     Submit,
+                    FillRole(ColorRole::Accent),
                 ))
                 .with_children(|builder| {
                     builder.spawn(TextBundle {
                         text: Text::from_section(
-                            "SUBMIT".to_string(),
+                            strings.get("leaderboard.submit"),
                             TextStyle {
-                                font: asset_server.load(FONT),
+                                font: theme.font.clone(),
                                 font_size: 40.0,
                                 color: Color::BEIGE,
-                                ..default()
                             },
                         ),
                         ..default()
@@ -237,10 +252,10 @@ fn update_round(
     mut round_executor: AsyncTaskRunner<Result<ehttp::Response, ehttp::Error>>,
     mut update_round_events: EventReader<UpdateRoundEvent>,
     mut user: ResMut<User>,
+    mut upload: ResMut<ScoreUpload>,
 ) {
-    match round_executor.poll() {
-        AsyncTaskStatus::Finished(_) => (),
-        _ => (),
+    if let AsyncTaskStatus::Finished(_) = round_executor.poll() {
+        upload.in_flight = false
     }
 
     for event in update_round_events.read() {
@@ -256,6 +271,7 @@ fn update_round(
                     .headers
                     .insert("Content-Type".into(), "application/json".into());
                 round_executor.start(ehttp::fetch_async(request));
+                upload.in_flight = true;
             }
         }
     }
@@ -265,7 +281,7 @@ fn spawn_leaderboard(
     mut commands: Commands,
     mut response_executor: AsyncTaskRunner<Result<ehttp::Response, ehttp::Error>>,
     leaderboard_query: Query<Entity, With<Leaderboard>>,
-    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
 ) {
     if !leaderboard_query.is_empty() {
         return;
@@ -276,38 +292,38 @@ fn spawn_leaderboard(
             let request = ehttp::Request::get("https://ode.halyte.net/");
             response_executor.start(ehttp::fetch_async(request));
         }
-        AsyncTaskStatus::Finished(response) => {
-            if let Ok(response) = response {
-                if let Ok(leaderboard) =
-                    serde_json::from_str::<Vec<UserScore>>(response.text().unwrap())
-                {
-                    commands
-                        .spawn((
-                            NodeBundle {
-                                style: Style {
-                                    justify_self: JustifySelf::Center,
-                                    align_self: AlignSelf::Center,
-                                    flex_direction: FlexDirection::Column,
-                                    ..default()
-                                },
+        AsyncTaskStatus::Finished(Ok(response)) => {
+            if let Ok(leaderboard) =
+                serde_json::from_str::<Vec<UserScore>>(response.text().unwrap())
+            {
+                commands
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                justify_self: JustifySelf::Center,
+                                align_self: AlignSelf::Center,
+                                flex_direction: FlexDirection::Column,
                                 ..default()
                             },
-                            Leaderboard,
-                        ))
-                        .with_children(|builder| {
-                            for (idx, user_score) in leaderboard.iter().enumerate() {
-                                builder
-                                    .spawn(NodeBundle {
-                                        style: Style {
-                                            justify_content: JustifyContent::SpaceBetween,
-                                            align_items: AlignItems::Center,
-                                            width: Val::Px(300.0),
-                                            ..default()
-                                        },
+                            ..default()
+                        },
+                        Leaderboard,
+                    ))
+                    .with_children(|builder| {
+                        for (idx, user_score) in leaderboard.iter().enumerate() {
+                            builder
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        justify_content: JustifyContent::SpaceBetween,
+                                        align_items: AlignItems::Center,
+                                        width: Val::Px(300.0),
                                         ..default()
-                                    })
-                                    .with_children(|builder| {
-                                        builder.spawn(TextBundle {
+                                    },
+                                    ..default()
+                                })
+                                .with_children(|builder| {
+                                    builder.spawn((
+                                        TextBundle {
                                             style: Style {
                                                 margin: UiRect::right(Val::Px(25.0)),
                                                 width: Val::Px(20.0),
@@ -316,15 +332,17 @@ fn spawn_leaderboard(
                                             text: Text::from_section(
                                                 format!("{}", idx + 1),
                                                 TextStyle {
-                                                    font: asset_server.load(FONT),
+                                                    font: theme.font.clone(),
                                                     font_size: 25.0,
-                                                    color: OLIVE_GREEN,
-                                                    ..default()
+                                                    color: theme.text,
                                                 },
                                             ),
                                             ..default()
-                                        });
-                                        builder.spawn(TextBundle {
+                                        },
+                                        TextRole(ColorRole::Text),
+                                    ));
+                                    builder.spawn((
+                                        TextBundle {
                                             style: Style {
                                                 margin: UiRect::right(Val::Px(5.0)),
                                                 flex_grow: 0.0,
@@ -334,15 +352,17 @@ fn spawn_leaderboard(
                                             text: Text::from_section(
                                                 user_score.user.clone(),
                                                 TextStyle {
-                                                    font: asset_server.load(FONT),
+                                                    font: theme.font.clone(),
                                                     font_size: 25.0,
-                                                    color: OLIVE_GREEN,
-                                                    ..default()
+                                                    color: theme.text,
                                                 },
                                             ),
                                             ..default()
-                                        });
-                                        builder.spawn(TextBundle {
+                                        },
+                                        TextRole(ColorRole::Text),
+                                    ));
+                                    builder.spawn((
+                                        TextBundle {
                                             style: Style {
                                                 margin: UiRect::left(Val::Auto),
                                                 padding: UiRect::right(Val::Px(20.0)),
@@ -351,30 +371,24 @@ fn spawn_leaderboard(
                                             text: Text::from_section(
                                                 format!("{}", user_score.score),
                                                 TextStyle {
-                                                    font: asset_server.load(FONT),
+                                                    font: theme.font.clone(),
                                                     font_size: 25.0,
-                                                    color: OLIVE_GREEN,
-                                                    ..default()
+                                                    color: theme.text,
                                                 },
                                             ),
                                             ..default()
-                                        });
-                                    });
-                            }
-                        });
-                }
+                                        },
+                                        TextRole(ColorRole::Text),
+                                    ));
+                                });
+                        }
+                    });
             }
         }
         _ => (),
     }
 }
 
-#[derive(Serialize, Deserialize, Default)]
-pub struct UserLogin {
-    user: String,
-    pin: i64,
-}
-
 pub fn cleanup(
     mut commands: Commands,
     leaderboard_query: Query<Entity, With<Leaderboard>>,
@@ -404,92 +418,83 @@ fn handle_submit_button(
     mut submit_executor: AsyncTaskRunner<Result<ehttp::Response, ehttp::Error>>,
     mut login_data: ResMut<User>,
     mut villagers: ResMut<Villagers>,
-    asset_server: Res<AssetServer>,
+    strings: Strings,
+    theme: Res<Theme>,
     leaderboard_query: Query<Entity, With<Leaderboard>>,
 ) {
-    match submit_executor.poll() {
-        AsyncTaskStatus::Finished(result) => {
-            if let Ok(result) = result {
-                if let Ok(user) = serde_json::from_slice::<User>(&result.bytes) {
-                    // Despawn form
-                    for login_entity in login_form_query.iter().chain(leaderboard_query.iter()) {
-                        if let Some(login_entity) = commands.get_entity(login_entity) {
-                            login_entity.despawn_recursive();
-                        }
-                    }
-
-                    // Write resource
-                    login_data.user = user.user;
-                    login_data.pin = user.pin;
-                    login_data.score = user.score;
+    if let AsyncTaskStatus::Finished(Ok(result)) = submit_executor.poll() {
+        if let Ok(user) = serde_json::from_slice::<User>(&result.bytes) {
+            // Despawn form
+            for login_entity in login_form_query.iter().chain(leaderboard_query.iter()) {
+                if let Some(login_entity) = commands.get_entity(login_entity) {
+                    login_entity.despawn_recursive();
+                }
+            }
 
-                    for (_index, villager) in villagers.villagers.iter_mut().enumerate() {
-                        if user.score >= (villager.3 - 1) as i64 {
-                            villager.2 = true;
-                        }
-                    }
+            // Write resource
+            login_data.user = user.user;
+            login_data.pin = user.pin;
+            login_data.score = user.score;
 
-                    spawn_user_text(&mut commands, &login_data, &asset_server);
+            for villager in villagers.villagers.iter_mut() {
+                if user.score >= (villager.3 - 1) as i64 {
+                    villager.2 = true;
                 }
             }
+
+            spawn_user_text(&mut commands, &login_data, &strings, &theme);
         }
-        _ => (),
     }
 
     for button_interaction in submit_button_query.iter() {
-        match *button_interaction {
-            Interaction::Pressed => {
-                for editing_entity in editing_query.iter() {
-                    if let Some(mut editing_entity) = commands.get_entity(editing_entity) {
-                        editing_entity.remove::<Editing>();
-                    }
+        if *button_interaction == Interaction::Pressed {
+            for editing_entity in editing_query.iter() {
+                if let Some(mut editing_entity) = commands.get_entity(editing_entity) {
+                    editing_entity.remove::<Editing>();
+                }
 
-                    let username_button = username_button_query.single();
-                    let password_button = password_button_query.single();
-                    let mut username = None;
-                    let mut password = None;
-                    for child in children_query.iter_descendants(username_button) {
-                        if let Ok(text) = text_query.get(child) {
-                            if text.sections[0].value.len() > 2 {
-                                username = Some(text.sections[0].value.clone());
-                            }
+                let username_button = username_button_query.single();
+                let password_button = password_button_query.single();
+                let mut username = None;
+                let mut password = None;
+                for child in children_query.iter_descendants(username_button) {
+                    if let Ok(text) = text_query.get(child) {
+                        if text.sections[0].value.len() > 2 {
+                            username = Some(text.sections[0].value.clone());
                         }
                     }
+                }
 
-                    if password_button.value.len() > 3 {
-                        password = Some(password_button.value.clone());
-                    }
+                if password_button.value.len() > 3 {
+                    password = Some(password_button.value.clone());
+                }
 
-                    if let Some(username) = username {
-                        if let Some(password) = password {
-                            if let Ok(pin) = password.parse::<i64>() {
-                                let new_user = User {
-                                    user: username,
-                                    pin,
-                                    score: login_data.score,
-                                };
-                                if let Ok(new_user) = serde_json::ser::to_vec(&new_user) {
-                                    let mut request = ehttp::Request::post(
-                                        "https://ode.halyte.net/user",
-                                        new_user,
-                                    );
-                                    request
-                                        .headers
-                                        .insert("Content-Type".into(), "application/json".into());
-                                    submit_executor.start(ehttp::fetch_async(request));
-                                }
+                if let Some(username) = username {
+                    if let Some(password) = password {
+                        if let Ok(pin) = password.parse::<i64>() {
+                            let new_user = User {
+                                user: username,
+                                pin,
+                                score: login_data.score,
+                            };
+                            if let Ok(new_user) = serde_json::ser::to_vec(&new_user) {
+                                let mut request =
+                                    ehttp::Request::post("https://ode.halyte.net/user", new_user);
+                                request
+                                    .headers
+                                    .insert("Content-Type".into(), "application/json".into());
+                                submit_executor.start(ehttp::fetch_async(request));
                             }
                         }
                     }
                 }
             }
-            _ => (),
         }
     }
 }
 
 /// Spawn text with username/score
-fn spawn_user_text(commands: &mut Commands, user: &ResMut<User>, asset_server: &Res<AssetServer>) {
+fn spawn_user_text(commands: &mut Commands, user: &ResMut<User>, strings: &Strings, theme: &Theme) {
     commands
         .spawn((
             NodeBundle {
@@ -505,32 +510,39 @@ fn spawn_user_text(commands: &mut Commands, user: &ResMut<User>, asset_server: &
             LoginText,
         ))
         .with_children(|builder| {
-            builder.spawn(TextBundle {
-                text: Text::from_section(
-                    format!("Logged in as: {}", user.user),
-                    TextStyle {
-                        font: asset_server.load(FONT),
-                        font_size: 40.0,
-                        color: OLIVE_GREEN,
-                        ..default()
-                    },
-                ),
-                style: Style { ..default() },
-                ..default()
-            });
-            builder.spawn(TextBundle {
-                text: Text::from_section(
-                    format!("Personal Best: {}", user.score),
-                    TextStyle {
-                        font: asset_server.load(FONT),
-                        font_size: 30.0,
-                        color: OLIVE_GREEN,
-                        ..default()
-                    },
-                ),
-                style: Style { ..default() },
-                ..default()
-            });
+            builder.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        strings.format("leaderboard.logged_in", &[("user", user.user.clone())]),
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 40.0,
+                            color: theme.text,
+                        },
+                    ),
+                    style: Style { ..default() },
+                    ..default()
+                },
+                TextRole(ColorRole::Text),
+            ));
+            builder.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        strings.format(
+                            "leaderboard.personal_best",
+                            &[("score", user.score.to_string())],
+                        ),
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 30.0,
+                            color: theme.text,
+                        },
+                    ),
+                    style: Style { ..default() },
+                    ..default()
+                },
+                TextRole(ColorRole::Text),
+            ));
         });
 }
 
@@ -543,19 +555,20 @@ fn update_button_look(
         (&mut BackgroundColor, &Interaction),
         (Changed<Interaction>, With<Submit>, Without<Editable>),
     >,
+    theme: Res<Theme>,
 ) {
     for (mut button_background, button_interaction) in button_query.iter_mut() {
         match *button_interaction {
-            Interaction::Pressed => button_background.0 = BASIL_GREEN,
-            Interaction::Hovered => button_background.0 = Color::SALMON,
-            Interaction::None => button_background.0 = OLIVE_GREEN,
+            Interaction::Pressed => button_background.0 = theme.accent,
+            Interaction::Hovered => button_background.0 = theme.emphasis,
+            Interaction::None => button_background.0 = theme.text,
         }
     }
     for (mut button_background, button_interaction) in submit_button_query.iter_mut() {
         match *button_interaction {
-            Interaction::Pressed => button_background.0 = OLIVE_GREEN,
-            Interaction::Hovered => button_background.0 = Color::SALMON,
-            Interaction::None => button_background.0 = BASIL_GREEN,
+            Interaction::Pressed => button_background.0 = theme.text,
+            Interaction::Hovered => button_background.0 = theme.emphasis,
+            Interaction::None => button_background.0 = theme.accent,
         }
     }
 }
@@ -564,7 +577,8 @@ fn update_text_edit_state(
     mut commands: Commands,
     button_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Editable>)>,
     editing_query: Query<Entity, With<Editing>>,
-    input: Res<Input<KeyCode>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    actions: Actions,
 ) {
     for (button_entity, button_interaction) in button_query.iter() {
         match *button_interaction {
@@ -584,7 +598,8 @@ fn update_text_edit_state(
         }
     }
 
-    if input.just_pressed(KeyCode::Escape) {
+    // Fixed to Escape, Back may be bound to a letter that belongs in the field
+    if keyboard_input.just_pressed(KeyCode::Escape) || actions.gamepad_just_pressed(Action::Back) {
         for editing_entity in editing_query.iter() {
             if let Some(mut editing_entity) = commands.get_entity(editing_entity) {
                 editing_entity.remove::<Editing>();
@@ -608,7 +623,7 @@ fn listen_received_character_events(
                     }
                 }
             } else if let Some(mut password) = password {
-                if event.char.is_digit(10) {
+                if event.char.is_ascii_digit() {
                     password.value.push(event.char);
                     for child in children_query.iter_descendants(button_entity) {
                         if let Ok(mut text) = text_query.get_mut(child) {
@@ -646,39 +661,31 @@ fn listen_keyboard_input_events(
     }
 }
 
-pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // Spawn Music
-    commands.spawn(AudioBundle {
-        source: asset_server.load("music/Petunia.ogg"),
-        settings: PlaybackSettings {
-            volume: Volume::new_relative(0.5),
-            ..Default::default()
-        },
-        ..default()
-    });
+pub fn setup_ui(mut commands: Commands, strings: Strings, theme: Res<Theme>) {
     // Spawn Title Text
-    let title = format!("Leaderboard");
+    let title = strings.get("leaderboard.title");
 
-    commands.spawn({
+    commands.spawn((
         TextBundle {
             text: Text::from_section(
                 title,
                 TextStyle {
-                    font: asset_server.load(FONT),
+                    font: theme.font.clone(),
                     font_size: 64.0,
-                    color: OLIVE_GREEN,
+                    color: theme.text,
                 },
             )
             .with_alignment(TextAlignment::Center),
             style: Style {
-                top: Val::Vh(-40.0),
+                top: Val::Px(-288.0),
                 justify_self: JustifySelf::Center,
                 align_self: AlignSelf::Center,
                 ..default()
             },
             ..default()
-        }
-    });
+        },
+        TextRole(ColorRole::Text),
+    ));
 
     // Spawn Menu Button
     commands
@@ -696,94 +703,32 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
             BackButton::MainMenu,
         ))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(150.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    BackButton::MainMenu,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        TextBundle::from_section(
-                            "Back",
-                            TextStyle {
-                                font: asset_server.load("fonts/Leila-Regular.ttf"),
-                                font_size: 40.0,
-                                color: OLIVE_GREEN,
-                            },
-                        ),
-                        BackButton::MainMenu,
-                    ));
-                });
+            spawn_button(
+                parent,
+                &theme,
+                strings.get("common.back"),
+                BackButton::MainMenu,
+            );
         });
 }
 
 pub fn interact_button(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut interaction_query: Query<
-        (&Interaction, &BackButton, &mut BorderColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    interaction_query: Query<(&Interaction, &BackButton), (Changed<Interaction>, With<Button>)>,
     mut next_state: ResMut<NextState<AppState>>,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Actions,
+    rebinding: Res<Rebinding>,
+    editing_query: Query<(), With<Editing>>,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
-    if keyboard_input.just_released(KeyCode::Escape) {
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Back.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
+    if !focus::keyboard_blocked(&rebinding, &editing_query) && actions.just_released(Action::Back) {
+        sfx.send(PlaySfx(SfxKind::Back));
         next_state.set(AppState::MainMenu);
     }
     // Buttons
-    for (interaction, answer_button, mut border_color) in interaction_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.spawn((
-                    AudioBundle {
-                        source: asset_server.load("music/Select.ogg"),
-                        settings: PlaybackSettings {
-                            mode: PlaybackMode::Despawn,
-                            ..Default::default()
-                        },
-                        ..default()
-                    },
-                    SoundEffect,
-                ));
-                *border_color = Color::WHITE.into();
-                match answer_button {
-                    BackButton::MainMenu => next_state.set(AppState::MainMenu),
-                }
-            }
-            Interaction::Hovered => {
-                *border_color = Color::SALMON.into();
-            }
-            Interaction::None => {
-                *border_color = OLIVE_GREEN.into();
+    for (interaction, back_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match back_button {
+                BackButton::MainMenu => next_state.set(AppState::MainMenu),
             }
         }
     }
@@ -794,7 +739,6 @@ pub fn clear_shapes(
     mut query: Query<Entity, With<BackButton>>,
     mut query_title: Query<Entity, With<Text>>,
     mut query_stamps: Query<Entity, With<Sprite>>,
-    mut query_music: Query<Entity, (With<PlaybackSettings>, Without<SoundEffect>)>,
 ) {
     for entity in query.iter_mut() {
         if let Some(entity) = commands.get_entity(entity) {
@@ -811,9 +755,4 @@ pub fn clear_shapes(
             entity.despawn_recursive();
         }
     }
-    for entity in query_music.iter_mut() {
-        if let Some(entity) = commands.get_entity(entity) {
-            entity.despawn_recursive();
-        }
-    }
 }
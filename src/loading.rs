@@ -1,16 +1,24 @@
-use crate::main_menu::FONT;
+use crate::assets::GameAssets;
+use crate::fruits::{FruitCatalogue, FruitPack, FRUIT_CATALOGUE};
+use crate::layout;
+use crate::locale::Strings;
+use crate::ui::{BorderRole, ColorRole, FillRole, Scenery, TextRole, Theme};
 use crate::AppState;
-use bevy::render::camera::ScalingMode;
-use bevy::render::color::*;
+use bevy::{asset::LoadState, utils::HashSet};
 use bevy::{core_pipeline::clear_color::ClearColorConfig, prelude::*};
 
 #[derive(Component)]
 pub struct Loading;
 
-#[derive(Resource)]
-pub struct LoadingTimer {
-    pub loading_timer: Timer,
-}
+// Fill of the progress bar
+#[derive(Component)]
+pub struct LoadingBar;
+
+#[derive(Component)]
+pub struct LoadingText;
+
+// How long the list of failed assets stays up before moving on anyway
+const FAILED_HOLD_SECS: f32 = 3.0;
 
 #[derive(Component)]
 pub struct AnimationIndices {
@@ -21,23 +29,19 @@ pub struct AnimationIndices {
 #[derive(Component, Deref, DerefMut)]
 pub struct AnimationTimer(Timer);
 
-pub const OLIVE_GREEN: Color = Color::rgb(82.0 / 255.0, 88.0 / 255.0, 32.0 / 255.0);
-
 pub fn setup_loading(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
 ) {
     // Spawn Camera in Foreground
     commands.spawn(Camera2dBundle {
         camera_2d: Camera2d {
-            clear_color: ClearColorConfig::Custom(Color::BISQUE),
+            clear_color: ClearColorConfig::Custom(theme.background),
         },
         projection: OrthographicProjection {
-            scaling_mode: ScalingMode::Fixed {
-                width: 1280.0,
-                height: 720.0,
-            },
+            scaling_mode: layout::canvas_scaling(),
             ..default()
         },
         transform: Transform::from_translation(Vec3::new(0.0, 0.0, 5.0)),
@@ -52,7 +56,7 @@ pub fn setup_loading(
     // });
 
     // Spawn Animated Background
-    let texture_handle_sun = asset_server.load("background/background.png");
+    let texture_handle_sun = assets.background.clone();
     let texture_atlas_sun = TextureAtlas::from_grid(
         texture_handle_sun,
         Vec2::new(1280.0, 720.0),
@@ -67,16 +71,20 @@ pub fn setup_loading(
     commands.spawn((
         SpriteSheetBundle {
             texture_atlas: texture_atlas_handle_sun,
-            sprite: TextureAtlasSprite::new(animation_indices_sun.first),
+            sprite: TextureAtlasSprite {
+                color: theme.tint,
+                ..TextureAtlasSprite::new(animation_indices_sun.first)
+            },
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
             ..default()
         },
         animation_indices_sun,
+        Scenery,
         AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
     ));
 
     // Spawn Loading Sprite
-    let texture_handle = asset_server.load("icons/loading.png");
+    let texture_handle = assets.loading.clone();
     let texture_atlas =
         TextureAtlas::from_grid(texture_handle, Vec2::new(80.0, 80.0), 7, 1, None, None);
     let texture_atlas_handle = texture_atlases.add(texture_atlas);
@@ -94,43 +102,171 @@ pub fn setup_loading(
         AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
     ));
 
-    // Spawn Loading Text
-
-    commands.spawn((
-        {
-            TextBundle {
-                text: Text::from_section(
-                    "Loading",
-                    TextStyle {
-                        font: asset_server.load(FONT),
-                        font_size: 48.0,
-                        color: OLIVE_GREEN,
-                    },
-                )
-                .with_alignment(TextAlignment::Center),
+    // Spawn Loading Text and Progress Bar
+    commands
+        .spawn((
+            NodeBundle {
                 style: Style {
-                    justify_self: JustifySelf::Center,
-                    align_self: AlignSelf::Center,
-                    top: Val::Px(50.0),
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    top: Val::Px(120.0),
                     ..default()
                 },
                 ..default()
-            }
-        },
-        Loading,
-    ));
+            },
+            Loading,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 48.0,
+                        color: theme.text,
+                    },
+                )
+                .with_text_alignment(TextAlignment::Center),
+                LoadingText,
+                TextRole(ColorRole::Text),
+            ));
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(400.),
+                            height: Val::Px(24.),
+                            border: UiRect::all(Val::Px(4.)),
+                            margin: UiRect::top(Val::Px(10.0)),
+                            ..default()
+                        },
+                        border_color: theme.text.into(),
+                        background_color: theme.button.into(),
+                        ..default()
+                    },
+                    BorderRole(ColorRole::Text),
+                    FillRole(ColorRole::Button),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            background_color: theme.emphasis.into(),
+                            ..default()
+                        },
+                        LoadingBar,
+                        FillRole(ColorRole::Emphasis),
+                    ));
+                });
+        });
 }
 
 pub fn tick_loading(
     time: Res<Time>,
-    mut timer: ResMut<LoadingTimer>,
+    asset_server: Res<AssetServer>,
+    assets: Res<GameAssets>,
+    catalogue: Res<FruitCatalogue>,
+    packs: Res<Assets<FruitPack>>,
     mut next_state: ResMut<NextState<AppState>>,
+    mut bar_query: Query<&mut Style, With<LoadingBar>>,
+    mut text_query: Query<&mut Text, With<LoadingText>>,
+    mut failed_hold: Local<Option<Timer>>,
+    strings: Strings,
 ) {
-    timer.loading_timer.tick(time.delta());
+    // Keep the failures on screen for a moment before going on without them
+    if let Some(hold) = failed_hold.as_mut() {
+        hold.tick(time.delta());
+        if hold.finished() {
+            next_state.set(AppState::MainMenu);
+        }
+        return;
+    }
 
-    if timer.loading_timer.finished() {
+    let ids = assets.untyped_ids();
+    let mut loaded = 0;
+    let mut failed = Vec::new();
+    for id in ids.iter() {
+        match asset_server.get_load_state(*id) {
+            Some(LoadState::Loaded) => loaded += 1,
+            Some(LoadState::Failed) => failed.push(*id),
+            _ => {}
+        }
+    }
+    // The catalogue is done once every pack it pulls in has loaded or failed and its
+    // fruit are known, which is also when their icons join the manifest
+    let mut packs_loading = false;
+    let mut packs_failed = Vec::new();
+    let mut fruit_ids = HashSet::new();
+    for handle in catalogue.pack_handles(&packs) {
+        match asset_server.get_load_state(&handle) {
+            Some(LoadState::Loaded) => {
+                if let Some(pack) = packs.get(&handle) {
+                    fruit_ids.extend(pack.fruits.iter().map(|fruit| fruit.id.as_str()));
+                }
+            }
+            Some(LoadState::Failed) => packs_failed.push(handle.id().untyped()),
+            _ => packs_loading = true,
+        }
+    }
+    if !packs_loading {
+        if !packs_failed.is_empty() {
+            failed.extend(packs_failed);
+        } else if fruit_ids.is_empty() {
+            error!("No fruit found in {}", FRUIT_CATALOGUE);
+            failed.push(catalogue.root.id().untyped());
+        } else if catalogue.fruits.len() == fruit_ids.len() {
+            loaded += 1;
+        }
+    }
+    let total = ids.len() + 1;
+    // Several packs can fail, but the catalogue is still one step of the bar
+    let finished = (loaded + failed.len()).min(total);
+    let progress = finished as f32 / total as f32;
+
+    for mut style in bar_query.iter_mut() {
+        style.width = Val::Percent(progress * 100.0);
+    }
+    // The locales are among the assets still loading, so English stands in until they arrive
+    let label = strings
+        .get_or("loading.progress", "Loading {percent}%")
+        .replace("{percent}", &format!("{:.0}", progress * 100.0));
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = label.clone();
+    }
+
+    if finished < total {
+        return;
+    }
+    if failed.is_empty() {
         next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    let paths: Vec<String> = failed
+        .iter()
+        .map(|id| match asset_server.get_path(*id) {
+            Some(path) => path.to_string(),
+            None => format!("{:?}", id),
+        })
+        .collect();
+    for path in paths.iter() {
+        error!("Failed to load asset {}", path);
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!(
+            "{}\n{}",
+            strings.get_or("loading.failed", "Could not load:"),
+            paths.join("\n")
+        );
     }
+    *failed_hold = Some(Timer::from_seconds(FAILED_HOLD_SECS, TimerMode::Once));
 }
 
 pub fn animate_background_and_load(
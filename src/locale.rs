@@ -0,0 +1,248 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    ecs::system::SystemParam,
+    prelude::*,
+    utils::{BoxedFuture, HashMap},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::GameAssets,
+    fruits::{FruitCatalogue, FruitType},
+    storage,
+};
+
+const LANGUAGE_SAVE: &str = "language";
+// Strings missing from a locale fall back to this one
+const DEFAULT_LANGUAGE: &str = "en";
+/// Every language the picker cycles through, each loaded from `locales/{code}.locale.json`
+pub const LANGUAGES: [&str; 2] = ["en", "es"];
+
+pub struct LocalePlugin;
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<LocaleFile>()
+            .init_asset_loader::<LocaleFileLoader>()
+            .init_resource::<Language>()
+            .add_systems(Startup, load_language);
+    }
+}
+
+pub fn locale_path(code: &str) -> String {
+    format!("locales/{}.locale.json", code)
+}
+
+/// One language's strings, keyed the same across every locale file
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct LocaleFile {
+    // Shown in the language picker, written in the language itself
+    pub name: String,
+    // Leila only covers plain ASCII, languages with other glyphs name a
+    // font from assets::FALLBACK_FONTS that has them
+    #[serde(default)]
+    pub font: Option<String>,
+    pub strings: HashMap<String, String>,
+}
+
+#[derive(Default)]
+pub struct LocaleFileLoader;
+
+impl AssetLoader for LocaleFileLoader {
+    type Asset = LocaleFile;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<LocaleFile, std::io::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(serde_json::from_slice::<LocaleFile>(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["locale.json"]
+    }
+}
+
+/// Code of the language the player picked
+#[derive(Resource, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Language(pub String);
+
+impl Default for Language {
+    fn default() -> Self {
+        Self(DEFAULT_LANGUAGE.to_string())
+    }
+}
+
+impl Language {
+    pub fn next(&self) -> Language {
+        let index = LANGUAGES
+            .iter()
+            .position(|code| *code == self.0)
+            .unwrap_or(0);
+        Language(LANGUAGES[(index + 1) % LANGUAGES.len()].to_string())
+    }
+
+    pub fn save(&self) {
+        storage::save(LANGUAGE_SAVE, self);
+    }
+}
+
+/// Looks up UI text in the current language
+#[derive(SystemParam)]
+pub struct Strings<'w> {
+    language: Res<'w, Language>,
+    assets: Res<'w, GameAssets>,
+    locales: Res<'w, Assets<LocaleFile>>,
+}
+
+impl Strings<'_> {
+    fn locale(&self, code: &str) -> Option<&LocaleFile> {
+        self.assets
+            .locales
+            .get(code)
+            .and_then(|handle| self.locales.get(handle))
+    }
+
+    fn lookup(&self, key: &str) -> Option<&String> {
+        self.locale(&self.language.0)
+            .and_then(|locale| locale.strings.get(key))
+            .or_else(|| {
+                self.locale(DEFAULT_LANGUAGE)
+                    .and_then(|locale| locale.strings.get(key))
+            })
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        match self.lookup(key) {
+            Some(value) => value.clone(),
+            // Show the key itself so a missing string is easy to spot
+            None => key.to_string(),
+        }
+    }
+
+    /// String for `key`, or `fallback` when no locale has it
+    pub fn get_or(&self, key: &str, fallback: &str) -> String {
+        self.lookup(key)
+            .cloned()
+            .unwrap_or_else(|| fallback.to_string())
+    }
+
+    /// Fills `{name}` placeholders in the string for `key`
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        let mut value = self.get(key);
+        for (name, arg) in args {
+            value = value.replace(&format!("{{{}}}", name), arg);
+        }
+        value
+    }
+
+    /// Fruit packs only carry an English name, locales can add `fruit.{id}`
+    pub fn fruit_name(&self, catalogue: &FruitCatalogue, fruit_type: &FruitType) -> String {
+        self.get_or(
+            &format!("fruit.{}", fruit_type.0),
+            &catalogue.name(fruit_type),
+        )
+    }
+
+    /// "Name: Value" label for a settings button
+    pub fn setting(&self, name_key: &str, value: String) -> String {
+        self.format(
+            "options.setting",
+            &[("name", self.get(name_key)), ("value", value)],
+        )
+    }
+
+    pub fn on_off(&self, on: bool) -> String {
+        self.get(if on { "common.on" } else { "common.off" })
+    }
+
+    pub fn current_language_name(&self) -> String {
+        self.locale(&self.language.0)
+            .map_or_else(|| self.language.0.clone(), |locale| locale.name.clone())
+    }
+
+    pub fn font(&self) -> Handle<Font> {
+        match self
+            .locale(&self.language.0)
+            .and_then(|locale| locale.font.as_ref())
+        {
+            Some(path) => self.assets.font(path),
+            None => self.assets.font.clone(),
+        }
+    }
+}
+
+pub fn load_language(mut language: ResMut<Language>) {
+    if let Some(saved) = storage::load::<Language>(LANGUAGE_SAVE) {
+        if LANGUAGES.contains(&saved.0.as_str()) {
+            *language = saved;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::SystemState;
+
+    fn world_with_strings(strings: &[(&str, &str)]) -> World {
+        let mut locales = Assets::<LocaleFile>::default();
+        let handle = locales.add(LocaleFile {
+            name: "English".to_string(),
+            font: None,
+            strings: strings
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        });
+        let mut assets = GameAssets::default();
+        assets.locales.insert(DEFAULT_LANGUAGE, handle);
+
+        let mut world = World::new();
+        world.insert_resource(Language::default());
+        world.insert_resource(assets);
+        world.insert_resource(locales);
+        world
+    }
+
+    fn format(world: &mut World, key: &str, args: &[(&str, String)]) -> String {
+        let mut state = SystemState::<Strings>::new(world);
+        let strings = state.get(world);
+        strings.format(key, args)
+    }
+
+    #[test]
+    fn fills_every_placeholder() {
+        let mut world = world_with_strings(&[("pair", "{fruit_a} & {fruit_b}, {fruit_a}!")]);
+        let value = format(
+            &mut world,
+            "pair",
+            &[
+                ("fruit_a", "Apple".to_string()),
+                ("fruit_b", "Pear".to_string()),
+            ],
+        );
+        assert_eq!(value, "Apple & Pear, Apple!");
+    }
+
+    #[test]
+    fn leaves_unknown_placeholders_alone() {
+        let mut world = world_with_strings(&[("level", "Level {level}")]);
+        let value = format(&mut world, "level", &[("score", "3".to_string())]);
+        assert_eq!(value, "Level {level}");
+    }
+
+    #[test]
+    fn missing_keys_show_the_key() {
+        let mut world = world_with_strings(&[]);
+        let value = format(&mut world, "round.level", &[("level", "2".to_string())]);
+        assert_eq!(value, "round.level");
+    }
+}
@@ -1,55 +1,175 @@
-use bevy::{asset::AssetMetaCheck, log::LogPlugin, prelude::*};
+// Bevy systems take their resources and queries as arguments, so long
+// parameter lists and nested query types are expected
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use bevy::{asset::AssetMetaCheck, log::LogPlugin, prelude::*, ui::UiSystem};
+mod accessibility;
+mod assets;
+mod audio;
+mod display;
+mod focus;
+mod fruits;
 mod game_mod;
 mod how_to_play;
+mod input;
+mod layout;
 mod leaderboard;
 mod loading;
+mod locale;
 mod main_menu;
+mod options;
+mod pause_menu;
+mod results;
 mod settings;
+mod stats;
+mod storage;
+mod touch;
+mod ui;
+mod village;
+use accessibility::AccessibilitySettings;
+use assets::GameAssets;
+use audio::{AudioSettings, PlaySfx};
 use bevy_tweening::TweeningPlugin;
+use display::DisplaySettings;
+use focus::FocusActivation;
+use fruits::FruitPlugin;
 use game_mod::*;
+use input::InputMap;
+use layout::ScreenLayout;
 use leaderboard::LeaderboardPlugin;
-use loading::LoadingTimer;
+use locale::LocalePlugin;
+use options::{LanguagePicked, Rebinding};
 use settings::*;
-
-#[derive(Resource, Default)]
-pub struct Handles {
-    pub audio_handles: Vec<Handle<AudioSource>>,
-    pub image_handles: Vec<Handle<Image>>,
-}
+use stats::{PlayerStats, RoundFinished};
+use touch::{LongPress, TouchMode};
+use ui::Theme;
 
 fn main() {
+    let display = DisplaySettings::load();
     App::new()
         .insert_resource(AssetMetaCheck::Never)
         .add_plugins((
             DefaultPlugins
                 .set(ImagePlugin::default_nearest())
+                .set(WindowPlugin {
+                    primary_window: Some(display.window()),
+                    ..default()
+                })
                 .set(LogPlugin {
                     filter: "bevy_ecs=error,wgpu=error,naga=warn".into(),
                     ..default()
                 }),
             TweeningPlugin,
             LeaderboardPlugin,
+            FruitPlugin,
+            LocalePlugin,
         ))
         .init_resource::<GameData>()
         .init_resource::<GameSettings>()
-        .init_resource::<Handles>()
+        .init_resource::<GameAssets>()
         .init_resource::<Villagers>()
+        .init_resource::<FruitSelection>()
+        .init_resource::<InputMap>()
+        .init_resource::<AudioSettings>()
+        .init_resource::<Theme>()
+        .init_resource::<AccessibilitySettings>()
+        .insert_resource(display)
+        .init_resource::<Rebinding>()
+        .init_resource::<FocusActivation>()
+        .init_resource::<TouchMode>()
+        .init_resource::<ScreenLayout>()
+        .init_resource::<LongPress>()
+        .init_resource::<PlayerStats>()
+        .add_event::<PlaySfx>()
+        .add_event::<RoundFinished>()
+        .add_event::<LanguagePicked>()
         .add_state::<AppState>()
-        .add_systems(Startup, startup)
+        .add_systems(
+            Startup,
+            (
+                (assets::load_assets, ui::load_theme).chain(),
+                input::load_input_map,
+                audio::load_audio_settings,
+                accessibility::load_accessibility_settings,
+                stats::load_player_stats,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                audio::toggle_mute,
+                audio::apply_audio_settings,
+                audio::play_state_music,
+                audio::fade_music,
+                audio::adapt_round_music,
+            )
+                .chain(),
+        )
+        .add_systems(
+            PreUpdate,
+            (focus::navigate_focus, focus::activate_focus)
+                .chain()
+                .after(UiSystem::Focus),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                focus::draw_focus,
+                audio::play_sfx,
+                accessibility::scale_text.before(UiSystem::Layout),
+            ),
+        )
+        .add_systems(Last, display::limit_frame_rate)
+        .add_systems(
+            Update,
+            (
+                assets::load_fruit_icons.after(fruits::update_catalogue),
+                ui::interact_buttons,
+                display::apply_display_settings,
+                stats::record_rounds,
+                (ui::follow_language_font, ui::apply_theme).chain(),
+                (
+                    accessibility::assign_fruit_marks,
+                    accessibility::draw_fruit_marks,
+                )
+                    .chain(),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                touch::detect_touch,
+                layout::update_layout,
+                layout::apply_portrait_styles,
+                touch::resize_answer_zones,
+            )
+                .chain(),
+        )
         .add_systems(
             OnEnter(AppState::StartRound),
             (
                 game_mod::clear_shapes,
                 game_mod::play_game,
+                game_mod::update_answer_buttons,
                 game_mod::spawn_chibi_game,
             )
                 .chain(),
         )
+        .add_systems(OnEnter(AppState::ShowResults), game_mod::reveal_answer)
         .add_systems(
             OnEnter(AppState::GameOver),
-            (game_mod::upload_score, game_mod::game_over).chain(),
+            (
+                game_mod::upload_score,
+                game_mod::game_over,
+                stats::record_run,
+            )
+                .chain(),
         )
         .add_systems(OnEnter(AppState::QuitGame), main_menu::quit_game)
+        .add_systems(
+            Update,
+            main_menu::exit_game.run_if(in_state(AppState::QuitGame)),
+        )
         .add_systems(
             Update,
             (
@@ -72,10 +192,56 @@ fn main() {
                 settings::interact_button,
                 settings::set_fruits,
                 settings::hover_fruit,
+                settings::interact_slots,
+                settings::update_fruit_slots,
+                village::wander_villagers,
+                village::click_villagers,
+                village::clear_speech_bubbles,
                 loading::animate_background_and_load,
             )
                 .run_if(in_state(AppState::Settings)),
         )
+        .add_systems(
+            Update,
+            (
+                (options::interact_button, options::capture_binding).chain(),
+                options::interact_bindings,
+                options::update_binding_labels,
+                options::interact_volume,
+                options::interact_theme,
+                options::interact_accessibility,
+                options::interact_language.before(ui::follow_language_font),
+                // Rebuild the screen in the newly picked language
+                (options::clear_shapes, options::setup_ui)
+                    .chain()
+                    .after(ui::follow_language_font)
+                    .run_if(on_event::<LanguagePicked>()),
+                options::update_volume_widgets,
+                loading::animate_background_and_load,
+            )
+                .run_if(in_state(AppState::Options)),
+        )
+        .add_systems(
+            Update,
+            (
+                results::interact_button,
+                loading::animate_background_and_load,
+            )
+                .run_if(in_state(AppState::Results)),
+        )
+        .add_systems(
+            Update,
+            (
+                display::interact_button,
+                loading::animate_background_and_load,
+            )
+                .run_if(in_state(AppState::Display)),
+        )
+        .add_systems(
+            Update,
+            (stats::interact_button, loading::animate_background_and_load)
+                .run_if(in_state(AppState::Stats)),
+        )
         .add_systems(
             Update,
             (game_mod::show_results, loading::animate_background_and_load)
@@ -83,6 +249,7 @@ fn main() {
         )
         .insert_resource(ResultTimer {
             result_timer: Timer::from_seconds(0.5, TimerMode::Once),
+            reveal_timer: Timer::from_seconds(2.5, TimerMode::Once),
         })
         .add_systems(
             Update,
@@ -91,16 +258,22 @@ fn main() {
                 game_mod::tick_elapsed,
                 game_mod::interact_button,
                 game_mod::update_timer,
+                audio::tick_last_second,
                 loading::animate_background_and_load,
             )
                 .run_if(in_state(AppState::Pause)),
         )
+        .add_systems(
+            Update,
+            (
+                pause_menu::interact_button,
+                loading::animate_background_and_load,
+            )
+                .run_if(in_state(AppState::PauseMenu)),
+        )
         .insert_resource(PauseTimer {
             pause_timer: Timer::from_seconds(5.0, TimerMode::Once),
         })
-        .insert_resource(LoadingTimer {
-            loading_timer: Timer::from_seconds(4.0, TimerMode::Once),
-        })
         .add_systems(OnEnter(AppState::MainMenu), main_menu::setup_menu)
         .add_systems(OnEnter(AppState::LoadingScreen), loading::setup_loading)
         .add_systems(
@@ -111,11 +284,17 @@ fn main() {
             OnEnter(AppState::Settings),
             (
                 settings::setup_ui,
-                settings::spawn_chibi,
                 settings::spawn_fruit,
+                settings::spawn_fruit_slots,
+                village::spawn_villagers,
             ),
         )
         .add_systems(OnEnter(AppState::HowToPlay), how_to_play::setup_ui)
+        .add_systems(OnEnter(AppState::Options), options::setup_ui)
+        .add_systems(OnEnter(AppState::Display), display::setup_ui)
+        .add_systems(OnEnter(AppState::Results), results::setup_ui)
+        .add_systems(OnEnter(AppState::Stats), stats::setup_ui)
+        .add_systems(OnEnter(AppState::PauseMenu), pause_menu::setup_ui)
         .add_systems(
             Update,
             (
@@ -127,7 +306,10 @@ fn main() {
         )
         .add_systems(
             Update,
-            (loading::animate_background_and_load, loading::tick_loading)
+            (
+                loading::animate_background_and_load,
+                loading::tick_loading.after(assets::load_fruit_icons),
+            )
                 .run_if(in_state(AppState::LoadingScreen)),
         )
         .add_systems(OnExit(AppState::MainMenu), main_menu::clear_shapes)
@@ -138,51 +320,14 @@ fn main() {
             (leaderboard::cleanup, leaderboard::clear_shapes).chain(),
         )
         .add_systems(OnExit(AppState::Settings), settings::clear_shapes)
+        .add_systems(OnExit(AppState::Options), options::clear_shapes)
+        .add_systems(OnExit(AppState::Display), display::clear_shapes)
+        .add_systems(OnExit(AppState::Results), results::clear_shapes)
+        .add_systems(OnExit(AppState::Stats), stats::clear_shapes)
+        .add_systems(OnExit(AppState::PauseMenu), pause_menu::clear_shapes)
         .run();
 }
 
-fn startup(asset_server: Res<AssetServer>, mut handles: ResMut<Handles>) {
-    handles
-        .image_handles
-        .push(asset_server.load("icons/Title.png"));
-    handles
-        .image_handles
-        .push(asset_server.load("background/background.png"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Petunia.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Back.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Select.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Correct.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Incorrect.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Answer.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Bees.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Dandelions.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/NewChar.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Path.ogg"));
-    handles
-        .audio_handles
-        .push(asset_server.load("music/Rain.ogg"));
-}
-
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum AppState {
     #[default]
@@ -190,10 +335,15 @@ pub enum AppState {
     MainMenu,
     StartRound,
     Pause,
+    PauseMenu,
     ShowResults,
     GameOver,
+    Results,
     QuitGame,
     Leaderboard,
     HowToPlay,
     Settings,
+    Options,
+    Display,
+    Stats,
 }
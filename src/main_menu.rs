@@ -1,13 +1,21 @@
+use crate::accessibility::AccessibilitySettings;
+use crate::assets::GameAssets;
+use crate::audio::{AudioSettings, PlaySfx, SfxKind};
+use crate::display::DisplaySettings;
+use crate::focus::Focused;
+use crate::fruits::FruitCatalogue;
+use crate::input::{Action, Actions, InputMap};
+use crate::layout::{self, PortraitStyle};
+use crate::leaderboard::ScoreUpload;
 use crate::loading::AnimationIndices;
+use crate::locale::{Language, Strings};
 use crate::settings::GameSettings;
+use crate::ui::{spawn_button, ButtonBuilder, Theme};
 use crate::{game_mod, AppState};
-use bevy::audio::{PlaybackMode, Volume};
-use bevy::prelude::*;
+use bevy::{app::AppExit, prelude::*};
 
-pub const OLIVE_GREEN: Color = Color::rgb(82.0 / 255.0, 88.0 / 255.0, 32.0 / 255.0);
-pub const BASIL_GREEN: Color = Color::rgb(166.0 / 255.0, 179.0 / 255.0, 64.0 / 255.0);
-pub const SKY_BLUE: Color = Color::rgb(137.0 / 255.0, 204.0 / 255.0, 196.0 / 255.0);
-pub const FONT: &str = "fonts/Leila-Regular.ttf";
+// How long quitting waits on a score upload before giving up on it
+const QUIT_TIMEOUT_SECS: f32 = 3.0;
 
 #[derive(Component)]
 pub enum ActionButton {
@@ -15,7 +23,9 @@ pub enum ActionButton {
     Leaderboard,
     HowToPlay,
     Settings,
-    // Quit,
+    Options,
+    Stats,
+    Quit,
 }
 
 #[derive(Component)]
@@ -26,21 +36,13 @@ pub struct AnimationTimer(Timer);
 
 pub fn setup_menu(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
+    strings: Strings,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
 ) {
-    // Spawn Music
-    commands.spawn(AudioBundle {
-        source: asset_server.load("music/Dandelions.ogg"),
-        settings: PlaybackSettings {
-            volume: Volume::new_relative(0.5),
-            ..Default::default()
-        },
-        ..default()
-    });
-
     // Spawn Menu Title
-    let texture_handle = asset_server.load("icons/Title.png");
+    let texture_handle = assets.title.clone();
     let texture_atlas =
         TextureAtlas::from_grid(texture_handle, Vec2::new(960.0, 540.0), 6, 1, None, None);
     let texture_atlas_handle = texture_atlases.add(texture_atlas);
@@ -73,162 +75,74 @@ pub fn setup_menu(
                 ..default()
             },
             ActionButton::Play,
+            // A column under the title, the row is too wide for portrait
+            PortraitStyle::new(|style| {
+                layout::stack_in_column(style);
+                style.top = Val::Px(260.0);
+            }),
         ))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(150.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    ActionButton::Play,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        TextBundle::from_section(
-                            "Play",
-                            TextStyle {
-                                font: asset_server.load("fonts/Leila-Regular.ttf"),
-                                font_size: 40.0,
-                                color: OLIVE_GREEN,
-                            },
-                        ),
-                        ActionButton::Play,
-                    ));
-                });
-        })
-        // Spawn Leaderboard Button
-        .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(250.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    ActionButton::Leaderboard,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        TextBundle::from_section(
-                            "Leaderboard",
-                            TextStyle {
-                                font: asset_server.load("fonts/Leila-Regular.ttf"),
-                                font_size: 40.0,
-                                color: OLIVE_GREEN,
-                            },
-                        ),
-                        ActionButton::Leaderboard,
-                    ));
-                });
-        })
-        // Spawn Settings Button
-        .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(250.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    ActionButton::Settings,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        TextBundle::from_section(
-                            "Village",
-                            TextStyle {
-                                font: asset_server.load("fonts/Leila-Regular.ttf"),
-                                font_size: 40.0,
-                                color: OLIVE_GREEN,
-                            },
-                        ),
-                        ActionButton::Settings,
-                    ));
-                });
-        })
-        // Spawn How To Play Button
+            spawn_button(parent, &theme, strings.get("menu.play"), ActionButton::Play);
+            ButtonBuilder::new(strings.get("menu.leaderboard"), ActionButton::Leaderboard)
+                .size(250., 65.)
+                .spawn(parent, &theme);
+            ButtonBuilder::new(strings.get("menu.village"), ActionButton::Settings)
+                .size(250., 65.)
+                .spawn(parent, &theme);
+            ButtonBuilder::new(strings.get("menu.how_to_play"), ActionButton::HowToPlay)
+                .size(250., 65.)
+                .spawn(parent, &theme);
+            ButtonBuilder::new(strings.get("menu.settings"), ActionButton::Options)
+                .size(200., 65.)
+                .spawn(parent, &theme);
+        });
+
+    // Spawn Stats Button
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // top left button
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::Start,
+                    ..default()
+                },
+                ..default()
+            },
+            ActionButton::Stats,
+        ))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(250.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
+            spawn_button(
+                parent,
+                &theme,
+                strings.get("menu.stats"),
+                ActionButton::Stats,
+            );
+        });
+
+    // Spawn Quit Button, browsers close the tab instead
+    if !cfg!(target_arch = "wasm32") {
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        // top right button
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        justify_content: JustifyContent::End,
+                        align_items: AlignItems::Start,
                         ..default()
                     },
-                    ActionButton::HowToPlay,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        TextBundle::from_section(
-                            "How to Play",
-                            TextStyle {
-                                font: asset_server.load("fonts/Leila-Regular.ttf"),
-                                font_size: 40.0,
-                                color: OLIVE_GREEN,
-                            },
-                        ),
-                        ActionButton::HowToPlay,
-                    ));
-                });
-        });
+                    ..default()
+                },
+                ActionButton::Quit,
+            ))
+            .with_children(|parent| {
+                spawn_button(parent, &theme, strings.get("menu.quit"), ActionButton::Quit);
+            });
+    }
 }
 
 pub fn animate_menu_title(
@@ -252,62 +166,28 @@ pub fn animate_menu_title(
 }
 
 pub fn interact_menu(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut interaction_query: Query<
-        (&Interaction, &ActionButton, &mut BorderColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    interaction_query: Query<(&Interaction, &ActionButton), (Changed<Interaction>, With<Button>)>,
     mut next_state: ResMut<NextState<AppState>>,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Actions,
+    focus_query: Query<(), With<Focused>>,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
-    // if keyboard_input.just_released(KeyCode::Escape) {
-    //     next_state.set(AppState::QuitGame);
-    // }
-
-    if keyboard_input.just_released(KeyCode::Return) {
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Select.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
+    // Confirm presses the focused button instead when there is one
+    if actions.just_released(Action::Confirm) && focus_query.is_empty() {
+        sfx.send(PlaySfx(SfxKind::Select));
         next_state.set(AppState::StartRound);
     }
     // Buttons
-    for (interaction, answer_button, mut border_color) in interaction_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.spawn((
-                    AudioBundle {
-                        source: asset_server.load("music/Select.ogg"),
-                        settings: PlaybackSettings {
-                            mode: PlaybackMode::Despawn,
-                            ..Default::default()
-                        },
-                        ..default()
-                    },
-                    SoundEffect,
-                ));
-                *border_color = Color::WHITE.into();
-                match answer_button {
-                    ActionButton::HowToPlay => next_state.set(AppState::HowToPlay),
-                    ActionButton::Play => next_state.set(AppState::StartRound),
-                    ActionButton::Leaderboard => next_state.set(AppState::Leaderboard),
-                    ActionButton::Settings => next_state.set(AppState::Settings),
-                    // ActionButton::Quit => next_state.set(AppState::QuitGame),
-                }
-            }
-            Interaction::Hovered => {
-                *border_color = Color::SALMON.into();
-            }
-            Interaction::None => {
-                *border_color = OLIVE_GREEN.into();
+    for (interaction, action_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match action_button {
+                ActionButton::HowToPlay => next_state.set(AppState::HowToPlay),
+                ActionButton::Play => next_state.set(AppState::StartRound),
+                ActionButton::Leaderboard => next_state.set(AppState::Leaderboard),
+                ActionButton::Settings => next_state.set(AppState::Settings),
+                ActionButton::Options => next_state.set(AppState::Options),
+                ActionButton::Stats => next_state.set(AppState::Stats),
+                ActionButton::Quit => next_state.set(AppState::QuitGame),
             }
         }
     }
@@ -315,12 +195,13 @@ pub fn interact_menu(
 
 pub fn clear_shapes(
     mut commands: Commands,
-    mut query: Query<Entity, (With<ActionButton>, With<Parent>)>,
+    mut query: Query<Entity, (With<ActionButton>, Without<Parent>)>,
     mut query_title: Query<Entity, With<AnimationTimer>>,
-    mut query_music: Query<Entity, (With<PlaybackSettings>, Without<SoundEffect>)>,
     current_state: Res<State<AppState>>,
-    asset_server: Res<AssetServer>,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
     game_settings: Res<GameSettings>,
+    catalogue: Res<FruitCatalogue>,
 ) {
     for entity in query.iter_mut() {
         if let Some(entity) = commands.get_entity(entity) {
@@ -332,24 +213,39 @@ pub fn clear_shapes(
             entity.despawn_recursive();
         }
     }
-    for entity in query_music.iter_mut() {
-        if let Some(entity) = commands.get_entity(entity) {
-            entity.despawn_recursive();
-        }
-    }
 
-    match current_state.get() {
-        AppState::StartRound => {
-            game_mod::setup_ui(&mut commands, &asset_server, &game_settings);
-        }
-        _ => {}
+    if current_state.get() == &AppState::StartRound {
+        game_mod::setup_ui(&mut commands, &assets, &theme, &game_settings, &catalogue);
     }
 }
 
-pub fn quit_game(mut commands: Commands, mut query: Query<Entity>) {
-    for entity in query.iter_mut() {
-        if let Some(entity) = commands.get_entity(entity) {
-            entity.despawn_recursive();
-        }
+// Settings are saved as they change, save them all once more in case one
+// was caught mid-edit
+pub fn quit_game(
+    audio_settings: Res<AudioSettings>,
+    input_map: Res<InputMap>,
+    theme: Res<Theme>,
+    accessibility: Res<AccessibilitySettings>,
+    language: Res<Language>,
+    display: Res<DisplaySettings>,
+) {
+    audio_settings.save();
+    input_map.save();
+    theme.save();
+    accessibility.save();
+    language.save();
+    display.save();
+}
+
+// Exit once the last score has reached the server, or give up on it after a while
+pub fn exit_game(
+    time: Res<Time>,
+    upload: Res<ScoreUpload>,
+    mut waited: Local<f32>,
+    mut exit: EventWriter<AppExit>,
+) {
+    *waited += time.delta_seconds();
+    if !upload.in_flight || *waited > QUIT_TIMEOUT_SECS {
+        exit.send(AppExit);
     }
 }
@@ -0,0 +1,696 @@
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+
+use crate::{
+    accessibility::{AccessibilitySettings, AccessibilityToggle},
+    audio::{AudioSettings, AudioToggle, PlaySfx, SfxKind, VolumeChannel},
+    focus::DefaultFocus,
+    input::{bindable_keys, Action, Actions, InputMap},
+    layout::PortraitStyle,
+    locale::{Language, Strings},
+    ui::{spawn_button, BorderRole, ButtonBuilder, ColorRole, FillRole, TextRole, Theme},
+    AppState,
+};
+
+#[derive(Component)]
+pub enum BackButton {
+    MainMenu,
+}
+
+#[derive(Component)]
+pub struct OptionsScreen;
+
+#[derive(Component)]
+pub struct BindingButton(pub Action);
+
+#[derive(Component)]
+pub struct ResetControlsButton;
+
+// Bar that can be clicked or dragged to set a volume
+#[derive(Component)]
+pub struct VolumeSlider(pub VolumeChannel);
+
+#[derive(Component)]
+pub struct VolumeFill(pub VolumeChannel);
+
+// Nudges a volume up or down for keyboard and gamepad players
+#[derive(Component)]
+pub struct VolumeStep(pub VolumeChannel, pub f32);
+
+#[derive(Component)]
+pub struct AudioToggleButton(pub AudioToggle);
+
+#[derive(Component)]
+pub struct ThemeButton;
+
+#[derive(Component)]
+pub struct AccessibilityToggleButton(pub AccessibilityToggle);
+
+#[derive(Component)]
+pub struct TextSizeButton;
+
+#[derive(Component)]
+pub struct LanguageButton;
+
+#[derive(Component)]
+pub struct DisplayScreenButton;
+
+/// Sent when the language button picks a new language, the screen is rebuilt to match
+#[derive(Event)]
+pub struct LanguagePicked;
+
+// Action waiting for its new key, if any
+#[derive(Resource, Default)]
+pub struct Rebinding {
+    pub action: Option<Action>,
+}
+
+pub fn setup_ui(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    input_map: Res<InputMap>,
+    mut rebinding: ResMut<Rebinding>,
+    audio_settings: Res<AudioSettings>,
+    accessibility: Res<AccessibilitySettings>,
+    strings: Strings,
+) {
+    rebinding.action = None;
+
+    // Spawn Title Text
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                strings.get("options.title"),
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 64.0,
+                    color: theme.text,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            style: Style {
+                top: Val::Px(-252.0),
+                justify_self: JustifySelf::Center,
+                align_self: AlignSelf::Center,
+                ..default()
+            },
+            ..default()
+        },
+        OptionsScreen,
+        TextRole(ColorRole::Text),
+    ));
+
+    // Spawn Audio, Controls and Accessibility
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // horizontally center child text
+                    justify_self: JustifySelf::Center,
+                    // vertically center child text
+                    align_self: AlignSelf::Center,
+                    align_items: AlignItems::Start,
+                    column_gap: Val::Px(40.),
+                    ..default()
+                },
+                ..default()
+            },
+            OptionsScreen,
+            // Two sections to a row below the title, three don't fit across portrait
+            PortraitStyle::new(|style| {
+                style.flex_wrap = FlexWrap::Wrap;
+                style.max_width = Val::Percent(100.);
+                style.justify_content = JustifyContent::Center;
+                style.column_gap = Val::Px(0.);
+                style.row_gap = Val::Px(20.);
+                style.top = Val::Px(185.0);
+            }),
+        ))
+        .with_children(|parent| {
+            audio_section(parent, &theme, &strings, &audio_settings);
+            controls_section(parent, &theme, &strings, &input_map);
+            accessibility_section(parent, &theme, &strings, &accessibility);
+        });
+
+    // Spawn Menu Button
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::Start,
+                    ..default()
+                },
+                ..default()
+            },
+            BackButton::MainMenu,
+        ))
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                &theme,
+                strings.get("common.back"),
+                BackButton::MainMenu,
+            );
+        });
+
+    // Spawn Language Picker and Display Settings
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // top right buttons
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::End,
+                    ..default()
+                },
+                ..default()
+            },
+            OptionsScreen,
+        ))
+        .with_children(|parent| {
+            ButtonBuilder::new(language_label(&strings), LanguageButton)
+                .size(340., 65.)
+                .spawn(parent, &theme);
+            ButtonBuilder::new(strings.get("options.display"), DisplayScreenButton)
+                .size(340., 65.)
+                .margin(UiRect::top(Val::Px(12.0)))
+                .spawn(parent, &theme);
+        });
+}
+
+fn language_label(strings: &Strings) -> String {
+    strings.setting("options.language", strings.current_language_name())
+}
+
+fn theme_label(theme: &Theme, strings: &Strings) -> String {
+    strings.setting("options.theme", strings.get(theme.palette.label_key()))
+}
+
+fn audio_section(
+    builder: &mut ChildBuilder,
+    theme: &Theme,
+    strings: &Strings,
+    audio_settings: &AudioSettings,
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    strings.get("options.audio"),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 40.0,
+                        color: theme.emphasis,
+                    },
+                ),
+                TextRole(ColorRole::Emphasis),
+            ));
+            for channel in VolumeChannel::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(360.),
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(8.0)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(
+                                strings.get(channel.label_key()),
+                                TextStyle {
+                                    font: theme.font.clone(),
+                                    font_size: 32.0,
+                                    color: theme.text,
+                                },
+                            ),
+                            TextRole(ColorRole::Text),
+                        ));
+                        volume_step(parent, theme, channel, "-", -0.1);
+                        parent
+                            .spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(160.),
+                                        height: Val::Px(30.),
+                                        border: UiRect::all(Val::Px(4.)),
+                                        ..default()
+                                    },
+                                    border_color: theme.text.into(),
+                                    background_color: theme.button.into(),
+                                    ..default()
+                                },
+                                Interaction::default(),
+                                RelativeCursorPosition::default(),
+                                VolumeSlider(channel),
+                                BorderRole(ColorRole::Text),
+                                FillRole(ColorRole::Button),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            width: Val::Percent(audio_settings.get(channel) * 100.),
+                                            height: Val::Percent(100.),
+                                            ..default()
+                                        },
+                                        background_color: theme.emphasis.into(),
+                                        ..default()
+                                    },
+                                    VolumeFill(channel),
+                                    FillRole(ColorRole::Emphasis),
+                                ));
+                            });
+                        volume_step(parent, theme, channel, "+", 0.1);
+                    });
+            }
+            for toggle in AudioToggle::ALL {
+                ButtonBuilder::new(
+                    toggle_label(toggle, strings, audio_settings),
+                    AudioToggleButton(toggle),
+                )
+                .size(340., 50.)
+                .font_size(32.)
+                .margin(UiRect::top(Val::Px(12.0)))
+                .spawn(parent, theme);
+            }
+        });
+}
+
+fn volume_step(
+    builder: &mut ChildBuilder,
+    theme: &Theme,
+    channel: VolumeChannel,
+    label: &str,
+    step: f32,
+) {
+    ButtonBuilder::new(label, VolumeStep(channel, step))
+        .size(50., 50.)
+        .font_size(32.)
+        .spawn(builder, theme);
+}
+
+fn toggle_label(toggle: AudioToggle, strings: &Strings, audio_settings: &AudioSettings) -> String {
+    strings.setting(
+        toggle.label_key(),
+        strings.on_off(audio_settings.enabled(toggle)),
+    )
+}
+
+fn accessibility_section(
+    builder: &mut ChildBuilder,
+    theme: &Theme,
+    strings: &Strings,
+    accessibility: &AccessibilitySettings,
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    strings.get("options.accessibility"),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 40.0,
+                        color: theme.emphasis,
+                    },
+                ),
+                TextRole(ColorRole::Emphasis),
+            ));
+            ButtonBuilder::new(theme_label(theme, strings), ThemeButton)
+                .size(340., 50.)
+                .font_size(32.)
+                .margin(UiRect::top(Val::Px(8.0)))
+                .spawn(parent, theme);
+            for toggle in AccessibilityToggle::ALL {
+                ButtonBuilder::new(
+                    accessibility_label(toggle, strings, accessibility),
+                    AccessibilityToggleButton(toggle),
+                )
+                .size(340., 50.)
+                .font_size(32.)
+                .margin(UiRect::top(Val::Px(12.0)))
+                .spawn(parent, theme);
+            }
+            ButtonBuilder::new(text_size_label(strings, accessibility), TextSizeButton)
+                .size(340., 50.)
+                .font_size(32.)
+                .margin(UiRect::top(Val::Px(12.0)))
+                .spawn(parent, theme);
+        });
+}
+
+fn accessibility_label(
+    toggle: AccessibilityToggle,
+    strings: &Strings,
+    accessibility: &AccessibilitySettings,
+) -> String {
+    strings.setting(
+        toggle.label_key(),
+        strings.on_off(accessibility.enabled(toggle)),
+    )
+}
+
+fn text_size_label(strings: &Strings, accessibility: &AccessibilitySettings) -> String {
+    strings.setting(
+        "options.text_size",
+        format!("{:.0}%", accessibility.text_scale * 100.),
+    )
+}
+
+fn controls_section(
+    builder: &mut ChildBuilder,
+    theme: &Theme,
+    strings: &Strings,
+    input_map: &Res<InputMap>,
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    strings.get("options.controls"),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 40.0,
+                        color: theme.emphasis,
+                    },
+                ),
+                TextRole(ColorRole::Emphasis),
+            ));
+            for action in Action::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(360.),
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(8.0)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(
+                                strings.get(action.label_key()),
+                                TextStyle {
+                                    font: theme.font.clone(),
+                                    font_size: 32.0,
+                                    color: theme.text,
+                                },
+                            ),
+                            TextRole(ColorRole::Text),
+                        ));
+                        ButtonBuilder::new(
+                            input_map.key_name(action),
+                            (BindingButton(action), DefaultFocus),
+                        )
+                        .size(200., 50.)
+                        .font_size(32.)
+                        .spawn(parent, theme);
+                    });
+            }
+            ButtonBuilder::new(strings.get("options.reset_controls"), ResetControlsButton)
+                .size(250., 50.)
+                .font_size(32.)
+                .margin(UiRect::top(Val::Px(20.0)))
+                .spawn(parent, theme);
+        });
+}
+
+pub fn interact_button(
+    interaction_query: Query<(&Interaction, &BackButton), (Changed<Interaction>, With<Button>)>,
+    display_query: Query<&Interaction, (Changed<Interaction>, With<DisplayScreenButton>)>,
+    mut next_state: ResMut<NextState<AppState>>,
+    actions: Actions,
+    rebinding: Res<Rebinding>,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    // Keys go to the binding being edited instead of backing out
+    if actions.just_released(Action::Back) && rebinding.action.is_none() {
+        sfx.send(PlaySfx(SfxKind::Back));
+        next_state.set(AppState::MainMenu);
+    }
+    // Buttons
+    for (interaction, back_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match back_button {
+                BackButton::MainMenu => next_state.set(AppState::MainMenu),
+            }
+        }
+    }
+    for interaction in display_query.iter() {
+        if *interaction == Interaction::Pressed {
+            next_state.set(AppState::Display);
+        }
+    }
+}
+
+pub fn interact_bindings(
+    binding_query: Query<(&Interaction, &BindingButton), (Changed<Interaction>, With<Button>)>,
+    reset_query: Query<&Interaction, (Changed<Interaction>, With<ResetControlsButton>)>,
+    mut rebinding: ResMut<Rebinding>,
+    mut input_map: ResMut<InputMap>,
+) {
+    for (interaction, binding_button) in binding_query.iter() {
+        if *interaction == Interaction::Pressed {
+            // Clicking the binding being edited cancels it
+            rebinding.action = if rebinding.action == Some(binding_button.0) {
+                None
+            } else {
+                Some(binding_button.0)
+            };
+        }
+    }
+    for interaction in reset_query.iter() {
+        if *interaction == Interaction::Pressed {
+            *input_map = InputMap::default();
+            input_map.save();
+            rebinding.action = None;
+        }
+    }
+}
+
+pub fn interact_volume(
+    step_query: Query<(&Interaction, &VolumeStep), (Changed<Interaction>, With<Button>)>,
+    toggle_query: Query<(&Interaction, &AudioToggleButton), Changed<Interaction>>,
+    slider_query: Query<(&Interaction, &RelativeCursorPosition, &VolumeSlider)>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut audio_settings: ResMut<AudioSettings>,
+    mut dragged: Local<bool>,
+) {
+    let mut pressed = false;
+
+    for (interaction, volume_step) in step_query.iter() {
+        if *interaction == Interaction::Pressed {
+            let volume = audio_settings.get(volume_step.0) + volume_step.1;
+            // Snap to tenths so repeated steps land back on 0 and 1
+            audio_settings.set(volume_step.0, (volume * 10.).round() / 10.);
+            pressed = true;
+        }
+    }
+    for (interaction, toggle_button) in toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            audio_settings.flip(toggle_button.0);
+            pressed = true;
+        }
+    }
+
+    // Sliders follow the cursor while held and save once let go
+    for (interaction, cursor, slider) in slider_query.iter() {
+        if *interaction == Interaction::Pressed {
+            if let Some(position) = cursor.normalized {
+                if audio_settings.get(slider.0) != position.x.clamp(0.0, 1.0) {
+                    audio_settings.set(slider.0, position.x);
+                    *dragged = true;
+                }
+            }
+        }
+    }
+    if mouse_input.just_released(MouseButton::Left) && *dragged {
+        audio_settings.save();
+        *dragged = false;
+    }
+
+    if pressed {
+        audio_settings.save();
+    }
+}
+
+pub fn interact_theme(
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<ThemeButton>)>,
+    mut text_query: Query<&mut Text>,
+    mut theme: ResMut<Theme>,
+    strings: Strings,
+) {
+    for (interaction, children) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            let palette = theme.palette.next();
+            theme.set_palette(palette);
+            theme.save();
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(*child) {
+                    text.sections[0].value = theme_label(&theme, &strings);
+                }
+            }
+        }
+    }
+}
+
+pub fn interact_accessibility(
+    toggle_query: Query<
+        (&Interaction, &AccessibilityToggleButton, &Children),
+        Changed<Interaction>,
+    >,
+    text_size_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<TextSizeButton>)>,
+    mut text_query: Query<&mut Text>,
+    mut accessibility: ResMut<AccessibilitySettings>,
+    strings: Strings,
+) {
+    for (interaction, toggle_button, children) in toggle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            accessibility.flip(toggle_button.0);
+            accessibility.save();
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(*child) {
+                    text.sections[0].value =
+                        accessibility_label(toggle_button.0, &strings, &accessibility);
+                }
+            }
+        }
+    }
+    for (interaction, children) in text_size_query.iter() {
+        if *interaction == Interaction::Pressed {
+            accessibility.next_text_scale();
+            accessibility.save();
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(*child) {
+                    text.sections[0].value = text_size_label(&strings, &accessibility);
+                }
+            }
+        }
+    }
+}
+
+// The screen is rebuilt in the new language once Language changes, see main.rs
+pub fn interact_language(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<LanguageButton>)>,
+    mut language: ResMut<Language>,
+    mut picked: EventWriter<LanguagePicked>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            *language = language.next();
+            language.save();
+            picked.send(LanguagePicked);
+        }
+    }
+}
+
+pub fn update_volume_widgets(
+    audio_settings: Res<AudioSettings>,
+    mut fill_query: Query<(&VolumeFill, &mut Style)>,
+    toggle_query: Query<(&AudioToggleButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+    strings: Strings,
+) {
+    if !audio_settings.is_changed() {
+        return;
+    }
+    for (fill, mut style) in fill_query.iter_mut() {
+        style.width = Val::Percent(audio_settings.get(fill.0) * 100.);
+    }
+    for (toggle_button, children) in toggle_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = toggle_label(toggle_button.0, &strings, &audio_settings);
+            }
+        }
+    }
+}
+
+pub fn capture_binding(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut input_map: ResMut<InputMap>,
+) {
+    // Skip the frame the binding was picked, its own key is still being released
+    let Some(action) = rebinding.action else {
+        return;
+    };
+    if rebinding.is_changed() {
+        return;
+    }
+    // Wait for the release so the key doesn't also trigger its old action
+    if let Some(key) = bindable_keys().find(|key| keyboard_input.just_released(*key)) {
+        input_map.bind(action, key);
+        input_map.save();
+        rebinding.action = None;
+    }
+}
+
+pub fn update_binding_labels(
+    input_map: Res<InputMap>,
+    rebinding: Res<Rebinding>,
+    binding_query: Query<(&BindingButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+    strings: Strings,
+) {
+    if !input_map.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+    for (binding_button, children) in binding_query.iter() {
+        let label = if rebinding.action == Some(binding_button.0) {
+            strings.get("options.press_key")
+        } else {
+            input_map.key_name(binding_button.0)
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = label.clone();
+            }
+        }
+    }
+}
+
+pub fn clear_shapes(
+    mut commands: Commands,
+    mut query: Query<Entity, Or<(With<BackButton>, With<OptionsScreen>)>>,
+) {
+    for entity in query.iter_mut() {
+        if let Some(entity) = commands.get_entity(entity) {
+            entity.despawn_recursive();
+        }
+    }
+}
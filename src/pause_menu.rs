@@ -0,0 +1,147 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    audio::{MusicTrack, PlaySfx, SfxKind},
+    focus::DefaultFocus,
+    game_mod::{AnswerButton, GameData, GridIdentifier, PauseTimer},
+    input::{Action, Actions},
+    locale::Strings,
+    ui::{ButtonBuilder, ColorRole, TextRole, Theme},
+    AppState,
+};
+
+#[derive(Component)]
+pub enum PauseButton {
+    Resume,
+    Restart,
+    Quit,
+}
+
+#[derive(Component)]
+pub struct PauseOverlay;
+
+pub fn setup_ui(
+    mut commands: Commands,
+    strings: Strings,
+    theme: Res<Theme>,
+    mut hidden_query: Query<
+        (&mut Visibility, Option<&GridIdentifier>),
+        Or<(With<GridIdentifier>, (With<AnswerButton>, With<Button>))>,
+    >,
+    music_query: Query<&AudioSink, With<MusicTrack>>,
+) {
+    // Hide the fruit so the pause can't be used to count them
+    for (mut visibility, grid) in hidden_query.iter_mut() {
+        if !matches!(grid, Some(GridIdentifier::Grid)) {
+            *visibility = Visibility::Hidden;
+        }
+    }
+    for sink in music_query.iter() {
+        sink.pause();
+    }
+
+    // Spawn Overlay
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: theme.background.with_a(0.85).into(),
+                // keep clicks off the answer buttons underneath
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            PauseOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    strings.get("pause.title"),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 64.0,
+                        color: theme.text,
+                    },
+                ),
+                TextRole(ColorRole::Text),
+            ));
+            for (label, pause_button) in [
+                ("pause.resume", PauseButton::Resume),
+                ("pause.restart", PauseButton::Restart),
+                ("pause.quit", PauseButton::Quit),
+            ] {
+                let resume = matches!(pause_button, PauseButton::Resume);
+                let mut button = ButtonBuilder::new(strings.get(label), pause_button)
+                    .size(250., 65.)
+                    .margin(UiRect::top(Val::Px(20.0)))
+                    .spawn(parent, &theme);
+                if resume {
+                    button.insert(DefaultFocus);
+                }
+            }
+        });
+}
+
+pub fn interact_button(
+    interaction_query: Query<(&Interaction, &PauseButton), (Changed<Interaction>, With<Button>)>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut game_data: ResMut<GameData>,
+    mut timer: ResMut<PauseTimer>,
+    actions: Actions,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    // Keyboard Input
+    // Pause or Back again to carry on
+    if actions.just_released(Action::Pause) || actions.just_released(Action::Back) {
+        sfx.send(PlaySfx(SfxKind::Back));
+        next_state.set(AppState::Pause);
+    }
+    // Buttons
+    for (interaction, pause_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match pause_button {
+                PauseButton::Resume => next_state.set(AppState::Pause),
+                PauseButton::Restart => {
+                    game_data.restart();
+                    timer.pause_timer.reset();
+                    next_state.set(AppState::GameOver);
+                }
+                PauseButton::Quit => {
+                    game_data.quit();
+                    timer.pause_timer.reset();
+                    next_state.set(AppState::GameOver);
+                }
+            }
+        }
+    }
+}
+
+pub fn clear_shapes(
+    mut commands: Commands,
+    mut query: Query<Entity, With<PauseOverlay>>,
+    mut hidden_query: Query<
+        &mut Visibility,
+        Or<(With<GridIdentifier>, (With<AnswerButton>, With<Button>))>,
+    >,
+    music_query: Query<&AudioSink, With<MusicTrack>>,
+) {
+    for entity in query.iter_mut() {
+        if let Some(entity) = commands.get_entity(entity) {
+            entity.despawn_recursive();
+        }
+    }
+    for mut visibility in hidden_query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+    for sink in music_query.iter() {
+        sink.play();
+    }
+}
@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+use bevy_tweening::{
+    lens::TransformScaleLens, Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween,
+};
+use std::time::Duration;
+
+use crate::{
+    assets::GameAssets,
+    audio::{PlaySfx, SfxKind},
+    focus::DefaultFocus,
+    fruits::FruitCatalogue,
+    game_mod::{self, GameData, Result},
+    input::{Action, Actions},
+    locale::Strings,
+    settings::GameSettings,
+    ui::{ButtonBuilder, ColorRole, TextRole, Theme},
+    AppState,
+};
+
+#[derive(Component)]
+pub struct ResultsScreen;
+
+#[derive(Component)]
+pub enum ResultsButton {
+    Retry,
+    Leaderboard,
+    Menu,
+}
+
+pub fn setup_ui(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    game_data: Res<GameData>,
+    strings: Strings,
+) {
+    let run = game_data.run();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // horizontally center child text
+                    justify_self: JustifySelf::Center,
+                    // vertically center child text
+                    align_self: AlignSelf::Center,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            ResultsScreen,
+        ))
+        .with_children(|parent| {
+            // Spawn Title Text
+            parent.spawn((
+                TextBundle::from_section(
+                    strings.get("results.title"),
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 64.0,
+                        color: theme.text,
+                    },
+                ),
+                TextRole(ColorRole::Text),
+            ));
+
+            // Spawn What Ended The Run
+            let ending = match run.ending {
+                Some(Result::Incorrect) => Some("results.wrong"),
+                Some(Result::TimedOut) => Some("results.timed_out"),
+                _ => None,
+            };
+            if let Some(ending) = ending {
+                parent.spawn((
+                    TextBundle::from_section(
+                        strings.get(ending),
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 32.0,
+                            color: theme.error,
+                        },
+                    ),
+                    TextRole(ColorRole::Error),
+                ));
+            }
+
+            // Spawn Personal Best Banner
+            if run.new_best {
+                parent.spawn((
+                    TextBundle::from_section(
+                        strings.get("results.new_best"),
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 40.0,
+                            color: theme.emphasis,
+                        },
+                    ),
+                    Animator::new(
+                        Tween::new(
+                            EaseFunction::SineInOut,
+                            Duration::from_millis(600),
+                            TransformScaleLens {
+                                start: Vec3::ONE,
+                                end: Vec3::splat(1.1),
+                            },
+                        )
+                        .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
+                        .with_repeat_count(RepeatCount::Infinite),
+                    ),
+                    TextRole(ColorRole::Emphasis),
+                ));
+            }
+
+            // Spawn Run Stats
+            let accuracy = match run.accuracy() {
+                Some(accuracy) => format!("{:.0}%", accuracy * 100.),
+                None => "-".to_string(),
+            };
+            let reaction = match run.average_reaction() {
+                Some(reaction) => format!("{:.2}s", reaction.as_secs_f32()),
+                None => "-".to_string(),
+            };
+            for (name_key, value) in [
+                ("results.level", run.level.to_string()),
+                ("results.score", run.score.to_string()),
+                ("results.accuracy", accuracy),
+                ("results.reaction", reaction),
+                ("results.villagers", run.villagers_unlocked.to_string()),
+            ] {
+                parent.spawn((
+                    TextBundle::from_section(
+                        strings.setting(name_key, value),
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 32.0,
+                            color: theme.text,
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    }),
+                    TextRole(ColorRole::Text),
+                ));
+            }
+
+            // Spawn Retry / Leaderboard / Menu Row
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(20.),
+                        margin: UiRect::top(Val::Px(30.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    ButtonBuilder::new(
+                        strings.get("results.retry"),
+                        (ResultsButton::Retry, DefaultFocus),
+                    )
+                    .size(200., 65.)
+                    .spawn(parent, &theme);
+                    ButtonBuilder::new(strings.get("menu.leaderboard"), ResultsButton::Leaderboard)
+                        .size(250., 65.)
+                        .spawn(parent, &theme);
+                    ButtonBuilder::new(strings.get("results.menu"), ResultsButton::Menu)
+                        .size(200., 65.)
+                        .spawn(parent, &theme);
+                });
+        });
+}
+
+pub fn interact_button(
+    interaction_query: Query<(&Interaction, &ResultsButton), (Changed<Interaction>, With<Button>)>,
+    mut next_state: ResMut<NextState<AppState>>,
+    actions: Actions,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    // Keyboard Input
+    if actions.just_released(Action::Back) {
+        sfx.send(PlaySfx(SfxKind::Back));
+        next_state.set(AppState::MainMenu);
+    }
+    // Buttons
+    for (interaction, results_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match results_button {
+                ResultsButton::Retry => next_state.set(AppState::StartRound),
+                ResultsButton::Leaderboard => next_state.set(AppState::Leaderboard),
+                ResultsButton::Menu => next_state.set(AppState::MainMenu),
+            }
+        }
+    }
+}
+
+pub fn clear_shapes(
+    mut commands: Commands,
+    mut query: Query<Entity, With<ResultsScreen>>,
+    current_state: Res<State<AppState>>,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
+    game_settings: Res<GameSettings>,
+    catalogue: Res<FruitCatalogue>,
+) {
+    for entity in query.iter_mut() {
+        if let Some(entity) = commands.get_entity(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    // Retrying goes straight into a round, which needs its answer buttons back
+    if *current_state.get() == AppState::StartRound {
+        game_mod::setup_ui(&mut commands, &assets, &theme, &game_settings, &catalogue);
+    }
+}
@@ -1,17 +1,23 @@
 use std::time::Duration;
 
-use bevy::{
-    audio::{PlaybackMode, Volume},
-    prelude::*,
-};
+use bevy::prelude::*;
 use bevy_tweening::{
-    lens::{TransformRotationLens, UiPositionLens},
-    Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween,
+    lens::TransformRotationLens, Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween,
 };
 
 use crate::{
-    game_mod::{FruitType, GridIdentifier},
-    main_menu::{SoundEffect, BASIL_GREEN, FONT, OLIVE_GREEN, SKY_BLUE},
+    accessibility::spawn_fruit_mark,
+    assets::GameAssets,
+    audio::{PlaySfx, SfxKind},
+    focus::{DefaultFocus, Focused},
+    fruits::{FruitCatalogue, FruitDefinition, FruitType},
+    input::{Action, Actions, InputMap},
+    leaderboard::User,
+    locale::Strings,
+    touch::LongPress,
+    ui::{
+        spawn_button, BorderRole, ButtonBorder, ButtonBuilder, ColorRole, FillRole, TextRole, Theme,
+    },
     AppState,
 };
 
@@ -26,20 +32,74 @@ pub struct Hovered {}
 #[derive(Component)]
 pub struct Pressed {}
 
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum FruitSlot {
+    A,
+    B,
+}
+
+// Marks images showing the fruit currently assigned to a slot
+#[derive(Component)]
+pub struct SlotIcon(pub FruitSlot);
+
 #[derive(Component)]
-pub struct CharacterButton {}
+pub struct SwapButton;
+
+#[derive(Component)]
+pub struct SlotMessage;
+
+#[derive(Component)]
+pub struct ShuffleButton;
+
+#[derive(Resource, Default)]
+pub struct FruitSelection {
+    pub selected: Option<FruitType>,
+}
 
 #[derive(Resource)]
 pub struct GameSettings {
     pub fruit_a: FruitType,
     pub fruit_b: FruitType,
+    // Pick a new random pair of unlocked fruits every round
+    pub shuffle: bool,
+}
+
+impl GameSettings {
+    pub fn fruit(&self, slot: FruitSlot) -> &FruitType {
+        match slot {
+            FruitSlot::A => &self.fruit_a,
+            FruitSlot::B => &self.fruit_b,
+        }
+    }
+
+    /// Puts a fruit in a slot, swapping the pair when the fruit already sits in the other slot
+    pub fn assign(&mut self, slot: FruitSlot, fruit: FruitType) -> bool {
+        let other = match slot {
+            FruitSlot::A => FruitSlot::B,
+            FruitSlot::B => FruitSlot::A,
+        };
+        if *self.fruit(other) == fruit {
+            self.swap();
+            return true;
+        }
+        match slot {
+            FruitSlot::A => self.fruit_a = fruit,
+            FruitSlot::B => self.fruit_b = fruit,
+        }
+        false
+    }
+
+    pub fn swap(&mut self) {
+        std::mem::swap(&mut self.fruit_a, &mut self.fruit_b);
+    }
 }
 
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
-            fruit_a: FruitType::Apple,
-            fruit_b: FruitType::Pear,
+            fruit_a: FruitType::new("apple"),
+            fruit_b: FruitType::new("pear"),
+            shuffle: false,
         }
     }
 }
@@ -118,65 +178,67 @@ impl Default for Villagers {
     }
 }
 
-pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // Spawn Music
-    commands.spawn(AudioBundle {
-        source: asset_server.load("music/Rain.ogg"),
-        settings: PlaybackSettings {
-            volume: Volume::new_relative(0.2),
-            ..Default::default()
-        },
-        ..default()
-    });
+pub fn setup_ui(
+    mut commands: Commands,
+    strings: Strings,
+    theme: Res<Theme>,
+    input_map: Res<InputMap>,
+) {
     // Spawn Title Text
-    let title = format!("Village");
+    let title = strings.get("village.title");
 
-    commands.spawn({
+    commands.spawn((
         TextBundle {
             text: Text::from_section(
                 title,
                 TextStyle {
-                    font: asset_server.load(FONT),
+                    font: theme.font.clone(),
                     font_size: 64.0,
-                    color: OLIVE_GREEN,
+                    color: theme.text,
                 },
             )
             .with_alignment(TextAlignment::Center),
             style: Style {
-                top: Val::Vh(-30.0),
+                top: Val::Px(-216.0),
                 justify_self: JustifySelf::Center,
                 align_self: AlignSelf::Center,
                 ..default()
             },
             ..default()
-        }
-    });
+        },
+        TextRole(ColorRole::Text),
+    ));
     // Spawn Subtitle Text
-    let title = format!(
-        "Select the type of fruit you see in game.
-Light green is Fruit A, light blue is Fruit B."
+    let title = strings.format(
+        "village.instructions",
+        &[
+            ("fruit_a", input_map.key_name(Action::AnswerA)),
+            ("fruit_b", input_map.key_name(Action::AnswerB)),
+            ("swap", input_map.key_name(Action::Swap)),
+        ],
     );
 
-    commands.spawn({
+    commands.spawn((
         TextBundle {
             text: Text::from_section(
                 title,
                 TextStyle {
-                    font: asset_server.load(FONT),
+                    font: theme.font.clone(),
                     font_size: 32.0,
-                    color: OLIVE_GREEN,
+                    color: theme.text,
                 },
             )
             .with_alignment(TextAlignment::Center),
             style: Style {
-                top: Val::Vh(-20.0),
+                top: Val::Px(-144.0),
                 justify_self: JustifySelf::Center,
                 align_self: AlignSelf::Center,
                 ..default()
             },
             ..default()
-        }
-    });
+        },
+        TextRole(ColorRole::Text),
+    ));
 
     // Spawn Menu Buttons
     commands
@@ -195,94 +257,30 @@ Light green is Fruit A, light blue is Fruit B."
             BackButton::MainMenu,
         ))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(150.),
-                            height: Val::Px(65.),
-                            // horizontally center child text
-                            justify_content: JustifyContent::Center,
-                            // vertically center child text
-                            align_items: AlignItems::Center,
-                            border: UiRect {
-                                top: Val::Px(4.),
-                                left: Val::Px(4.),
-                                bottom: Val::Px(4.),
-                                right: Val::Px(4.),
-                            },
-                            ..default()
-                        },
-                        background_color: Color::BISQUE.into(),
-                        ..default()
-                    },
-                    BackButton::MainMenu,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        TextBundle::from_section(
-                            "Back",
-                            TextStyle {
-                                font: asset_server.load("fonts/Leila-Regular.ttf"),
-                                font_size: 40.0,
-                                color: OLIVE_GREEN,
-                            },
-                        ),
-                        BackButton::MainMenu,
-                    ));
-                });
+            spawn_button(
+                parent,
+                &theme,
+                strings.get("common.back"),
+                BackButton::MainMenu,
+            );
         });
 }
 
 pub fn interact_button(
-    mut commands: Commands,
-    asset_server: ResMut<AssetServer>,
-    mut interaction_query: Query<
-        (&Interaction, &BackButton, &mut BorderColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+    interaction_query: Query<(&Interaction, &BackButton), (Changed<Interaction>, With<Button>)>,
     mut next_state: ResMut<NextState<AppState>>,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Actions,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
-    if keyboard_input.just_released(KeyCode::Escape) {
-        commands.spawn((
-            AudioBundle {
-                source: asset_server.load("music/Back.ogg"),
-                settings: PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-                ..default()
-            },
-            SoundEffect,
-        ));
+    if actions.just_released(Action::Back) {
+        sfx.send(PlaySfx(SfxKind::Back));
         next_state.set(AppState::MainMenu);
     }
     // Buttons
-    for (interaction, answer_button, mut border_color) in interaction_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.spawn((
-                    AudioBundle {
-                        source: asset_server.load("music/Select.ogg"),
-                        settings: PlaybackSettings {
-                            mode: PlaybackMode::Despawn,
-                            ..Default::default()
-                        },
-                        ..default()
-                    },
-                    SoundEffect,
-                ));
-                *border_color = Color::WHITE.into();
-                match answer_button {
-                    BackButton::MainMenu => next_state.set(AppState::MainMenu),
-                }
-            }
-            Interaction::Hovered => {
-                *border_color = Color::SALMON.into();
-            }
-            Interaction::None => {
-                *border_color = OLIVE_GREEN.into();
+    for (interaction, back_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match back_button {
+                BackButton::MainMenu => next_state.set(AppState::MainMenu),
             }
         }
     }
@@ -295,100 +293,262 @@ pub fn hover_fruit(
         With<Button>,
     >,
     game_settings: ResMut<GameSettings>,
+    selection: Res<FruitSelection>,
+    theme: Res<Theme>,
 ) {
     // Buttons
     for (interaction, mut border_color, fruit_type, entity) in interaction_query.iter_mut() {
         if interaction.is_changed() {
             match *interaction {
                 Interaction::Pressed => {
-                    *border_color = Color::WHITE.into();
+                    *border_color = theme.pressed.into();
                     commands.entity(entity).insert(Pressed {});
                 }
                 Interaction::Hovered => {
-                    *border_color = Color::SALMON.into();
+                    *border_color = theme.emphasis.into();
                     commands.entity(entity).insert(Hovered {});
                 }
                 Interaction::None => {
-                    if *fruit_type == game_settings.fruit_a {
-                        *border_color = BASIL_GREEN.into();
-                    } else if *fruit_type == game_settings.fruit_b {
-                        *border_color = SKY_BLUE.into();
-                    } else {
-                        *border_color = OLIVE_GREEN.into();
-                    }
+                    *border_color = fruit_border(fruit_type, &game_settings, &selection, &theme);
                     commands.entity(entity).remove::<Hovered>();
                     commands.entity(entity).remove::<Pressed>();
                 }
             }
         } else if *interaction == Interaction::None {
-            if *fruit_type == game_settings.fruit_a {
-                *border_color = BASIL_GREEN.into();
-            } else if *fruit_type == game_settings.fruit_b {
-                *border_color = SKY_BLUE.into();
-            } else {
-                *border_color = OLIVE_GREEN.into();
-            }
+            *border_color = fruit_border(fruit_type, &game_settings, &selection, &theme);
             commands.entity(entity).remove::<Hovered>();
         }
     }
 }
 
+fn fruit_border(
+    fruit_type: &FruitType,
+    game_settings: &GameSettings,
+    selection: &FruitSelection,
+    theme: &Theme,
+) -> BorderColor {
+    if selection.selected.as_ref() == Some(fruit_type) {
+        theme.pressed.into()
+    } else if *fruit_type == game_settings.fruit_a {
+        theme.accent.into()
+    } else if *fruit_type == game_settings.fruit_b {
+        theme.highlight.into()
+    } else {
+        theme.text.into()
+    }
+}
+
 pub fn set_fruits(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
     mut interaction_query: Query<&FruitType, (With<Button>, With<Hovered>)>,
+    pressed_query: Query<(Entity, &FruitType, &Interaction), With<Button>>,
+    focused_query: Query<&FruitType, (With<Button>, Added<Focused>)>,
+    mut message_query: Query<&mut Text, With<SlotMessage>>,
     mouse_input: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    time: Res<Time>,
+    actions: Actions,
+    mut long_press: ResMut<LongPress>,
     mut game_settings: ResMut<GameSettings>,
+    mut selection: ResMut<FruitSelection>,
+    catalogue: Res<FruitCatalogue>,
+    strings: Strings,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
+    let mut message = None;
+
+    // Mouse Input
+    // Left click picks a fruit, right click puts it straight into Fruit B
     if mouse_input.just_released(MouseButton::Left) {
         for fruit_type in interaction_query.iter_mut() {
-            if fruit_type != &game_settings.fruit_b {
-                game_settings.fruit_a = fruit_type.clone();
-            }
-            commands.spawn((
-                AudioBundle {
-                    source: asset_server.load("music/Select.ogg"),
-                    settings: PlaybackSettings {
-                        mode: PlaybackMode::Despawn,
-                        ..Default::default()
-                    },
-                    ..default()
-                },
-                SoundEffect,
-            ));
+            selection.selected = Some(fruit_type.clone());
+            message = Some(picked_message(&strings, &catalogue, fruit_type));
         }
     }
     if mouse_input.just_released(MouseButton::Right) {
         for fruit_type in interaction_query.iter_mut() {
-            if fruit_type != &game_settings.fruit_a {
-                game_settings.fruit_b = fruit_type.clone();
+            message = Some(assign_fruit(
+                &mut game_settings,
+                &catalogue,
+                &strings,
+                FruitSlot::B,
+                fruit_type.clone(),
+            ));
+        }
+    }
+
+    // Touch Input
+    // A tap picks a fruit, holding it down puts it straight into Fruit B
+    if touches.iter().next().is_some() {
+        for (entity, fruit_type, interaction) in pressed_query.iter() {
+            if *interaction == Interaction::Pressed && long_press.hold(entity, time.delta()) {
+                message = Some(assign_fruit(
+                    &mut game_settings,
+                    &catalogue,
+                    &strings,
+                    FruitSlot::B,
+                    fruit_type.clone(),
+                ));
             }
-            commands.spawn((
-                AudioBundle {
-                    source: asset_server.load("music/Select.ogg"),
-                    settings: PlaybackSettings {
-                        mode: PlaybackMode::Despawn,
-                        ..Default::default()
-                    },
-                    ..default()
-                },
-                SoundEffect,
+        }
+    }
+    if touches.any_just_released() {
+        if let Some(entity) = long_press.release() {
+            if let Ok((_, fruit_type, _)) = pressed_query.get(entity) {
+                selection.selected = Some(fruit_type.clone());
+                message = Some(picked_message(&strings, &catalogue, fruit_type));
+            }
+        }
+    }
+
+    // Keyboard Input
+    // Moving focus onto a fruit picks it
+    for fruit_type in focused_query.iter() {
+        selection.selected = Some(fruit_type.clone());
+        message = Some(picked_message(&strings, &catalogue, fruit_type));
+    }
+    if let Some(selected) = selection.selected.clone() {
+        if actions.just_released(Action::AnswerA) {
+            message = Some(assign_fruit(
+                &mut game_settings,
+                &catalogue,
+                &strings,
+                FruitSlot::A,
+                selected,
             ));
+        } else if actions.just_released(Action::AnswerB) {
+            message = Some(assign_fruit(
+                &mut game_settings,
+                &catalogue,
+                &strings,
+                FruitSlot::B,
+                selected,
+            ));
+        }
+    }
+    if actions.just_released(Action::Swap) {
+        game_settings.swap();
+        message = Some(strings.get("village.swapped"));
+    }
+
+    if let Some(message) = message {
+        sfx.send(PlaySfx(SfxKind::Select));
+        for mut text in message_query.iter_mut() {
+            text.sections[0].value = message.clone();
         }
     }
 }
 
-pub fn spawn_chibi(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    villagers: Res<Villagers>,
+pub fn interact_slots(
+    slot_query: Query<(&Interaction, &FruitSlot), (Changed<Interaction>, With<Button>)>,
+    swap_query: Query<&Interaction, (Changed<Interaction>, With<SwapButton>)>,
+    shuffle_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<ShuffleButton>)>,
+    mut text_query: Query<&mut Text, Without<SlotMessage>>,
+    mut message_query: Query<&mut Text, With<SlotMessage>>,
+    mut game_settings: ResMut<GameSettings>,
+    selection: Res<FruitSelection>,
+    catalogue: Res<FruitCatalogue>,
+    strings: Strings,
 ) {
-    let columns = 10;
-    let rows = 1;
+    let mut message = None;
+
+    for (interaction, slot) in slot_query.iter() {
+        if *interaction == Interaction::Pressed {
+            message = Some(match selection.selected.clone() {
+                Some(selected) => {
+                    assign_fruit(&mut game_settings, &catalogue, &strings, *slot, selected)
+                }
+                None => strings.get("village.pick_first"),
+            });
+        }
+    }
+    for interaction in swap_query.iter() {
+        if *interaction == Interaction::Pressed {
+            game_settings.swap();
+            message = Some(strings.get("village.swapped"));
+        }
+    }
+    for (interaction, children) in shuffle_query.iter() {
+        if *interaction == Interaction::Pressed {
+            game_settings.shuffle = !game_settings.shuffle;
+            message = Some(if game_settings.shuffle {
+                strings.get("village.shuffle_on")
+            } else {
+                strings.get("village.shuffle_off")
+            });
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(*child) {
+                    text.sections[0].value = shuffle_label(&strings, game_settings.shuffle);
+                }
+            }
+        }
+    }
+
+    if let Some(message) = message {
+        for mut text in message_query.iter_mut() {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+fn picked_message(strings: &Strings, catalogue: &FruitCatalogue, fruit_type: &FruitType) -> String {
+    strings.format(
+        "village.picked",
+        &[("fruit", strings.fruit_name(catalogue, fruit_type))],
+    )
+}
 
-    // Spawn Villager Title
-    let title = format!("Villagers Unlocked");
+fn assign_fruit(
+    game_settings: &mut GameSettings,
+    catalogue: &FruitCatalogue,
+    strings: &Strings,
+    slot: FruitSlot,
+    fruit_type: FruitType,
+) -> String {
+    let name = strings.fruit_name(catalogue, &fruit_type);
+    let key = if game_settings.assign(slot, fruit_type) {
+        "village.already_picked"
+    } else {
+        match slot {
+            FruitSlot::A => "village.now_fruit_a",
+            FruitSlot::B => "village.now_fruit_b",
+        }
+    };
+    strings.format(key, &[("fruit", name)])
+}
 
+fn shuffle_label(strings: &Strings, shuffle: bool) -> String {
+    strings.setting("village.shuffle", strings.on_off(shuffle))
+}
+
+fn slot_border(slot: FruitSlot) -> ButtonBorder {
+    match slot {
+        FruitSlot::A => ButtonBorder::Accent,
+        FruitSlot::B => ButtonBorder::Highlight,
+    }
+}
+
+pub fn update_fruit_slots(
+    assets: Res<GameAssets>,
+    game_settings: Res<GameSettings>,
+    catalogue: Res<FruitCatalogue>,
+    mut icon_query: Query<(&SlotIcon, &mut UiImage)>,
+) {
+    if !game_settings.is_changed() && !catalogue.is_changed() {
+        return;
+    }
+    for (slot_icon, mut image) in icon_query.iter_mut() {
+        image.texture = assets.fruit_icon(&catalogue.icon(game_settings.fruit(slot_icon.0)));
+    }
+}
+
+pub fn spawn_fruit(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
+    catalogue: Res<FruitCatalogue>,
+    strings: Strings,
+    user: Res<User>,
+) {
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -396,89 +556,148 @@ pub fn spawn_chibi(
                 justify_self: JustifySelf::Center,
                 // vertically center child text
                 align_self: AlignSelf::Center,
+                top: Val::Px(-72.0),
+                // wrap onto more rows on narrow portrait screens
+                max_width: Val::Percent(95.),
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for fruit in catalogue.fruits.iter() {
+                fruit_button(
+                    parent,
+                    &assets,
+                    &theme,
+                    &strings,
+                    &catalogue,
+                    fruit,
+                    fruit.is_unlocked(user.score),
+                );
+            }
+        });
+}
+
+fn fruit_button(
+    builder: &mut ChildBuilder,
+    assets: &Res<GameAssets>,
+    theme: &Theme,
+    strings: &Strings,
+    catalogue: &FruitCatalogue,
+    fruit: &FruitDefinition,
+    unlocked: bool,
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
                 flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
                 ..default()
             },
             ..default()
         })
         .with_children(|parent| {
-            parent.spawn({
-                TextBundle {
-                    text: Text::from_section(
-                        title,
-                        TextStyle {
-                            font: asset_server.load(FONT),
-                            font_size: 32.0,
-                            color: OLIVE_GREEN,
-                        },
-                    )
-                    .with_alignment(TextAlignment::Center),
+            let mut button = parent.spawn((
+                ButtonBundle {
                     style: Style {
-                        top: Val::Vh(10.0),
-                        justify_self: JustifySelf::Center,
-                        align_self: AlignSelf::Center,
+                        width: Val::Px(150.),
+                        height: Val::Px(65.),
+                        // horizontally center child text
+                        justify_content: JustifyContent::Center,
+                        // vertically center child text
+                        align_items: AlignItems::Center,
+                        // center the node vertically and horizontally within the window
+                        position_type: PositionType::Relative,
+                        border: UiRect {
+                            top: Val::Px(4.),
+                            left: Val::Px(4.),
+                            bottom: Val::Px(4.),
+                            right: Val::Px(4.),
+                        },
+                        margin: UiRect {
+                            left: Val::Px(30.0),
+                            right: Val::Px(30.0),
+                            ..default()
+                        },
                         ..default()
                     },
+                    border_color: theme.text.into(),
+                    background_color: theme.button.into(),
                     ..default()
-                }
-            });
-
-            // Spawn Villager Grid
-            parent
-                .spawn((
-                    NodeBundle {
-                        style: Style {
-                            // Use the CSS Grid algorithm for laying out this node
-                            display: Display::Grid,
-                            top: Val::Vh(20.0),
+                },
+                FillRole(ColorRole::Button),
+            ));
+            // Locked fruits are shown but can't be picked
+            if unlocked {
+                button.insert((fruit.fruit_type(), DefaultFocus));
+            }
+            button.with_children(|parent| {
+                parent.spawn((
+                    ImageBundle {
+                        image: UiImage {
+                            texture: assets.fruit_icon(&fruit.icon),
                             ..default()
                         },
+                        background_color: (if unlocked {
+                            Color::WHITE.into()
+                        } else {
+                            Color::GRAY.into()
+                        }),
                         ..default()
                     },
-                    GridIdentifier::Grid,
-                ))
-                .with_children(|builder| {
-                    builder
-                        .spawn((
-                            NodeBundle {
-                                style: Style {
-                                    height: Val::Px(125.0),
-                                    width: Val::Vw(80.0),
-                                    // Make the grid have a 1:1 aspect ratio meaning it will scale as an exact square
-                                    // As the height is set explicitly, this means the width will adjust to match the height
-                                    aspect_ratio: Some(1.0),
-                                    // Use grid layout for this node
-                                    display: Display::Grid,
-                                    // Set the grid to have 10 columns all with sizes minmax(0, 1fr)
-                                    // This creates 10 exactly evenly sized columns
-                                    grid_template_columns: RepeatedGridTrack::flex(columns, 1.0),
-                                    // Set the grid to have 1 rows all with sizes minmax(0, 1fr)
-                                    // This creates 1 exactly evenly sized rows
-                                    grid_template_rows: RepeatedGridTrack::flex(rows, 1.0),
-                                    justify_self: JustifySelf::Center,
-                                    align_self: AlignSelf::Center,
-                                    ..default()
-                                },
-                                ..default()
+                    fruit.fruit_type(),
+                    Animator::new(
+                        Tween::new(
+                            EaseFunction::CubicInOut,
+                            Duration::from_millis(500),
+                            TransformRotationLens {
+                                start: Quat::from_rotation_z(10_f32.to_radians()),
+                                end: Quat::from_rotation_z(350_f32.to_radians()),
                             },
-                            GridIdentifier::Grid,
-                        ))
-                        .with_children(|builder| {
-                            for (index, villager) in villagers.villagers.iter().enumerate() {
-                                item_rect(
-                                    builder,
-                                    &asset_server,
-                                    villager.0.clone(),
-                                    villager.2.clone(),
-                                    index % 2 == 0,
-                                );
-                            }
-                        });
-                });
+                        )
+                        .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
+                        .with_repeat_count(RepeatCount::Infinite),
+                    ),
+                ));
+                // Slot filled in by accessibility::assign_fruit_marks
+                spawn_fruit_mark(parent, theme, None).insert(fruit.fruit_type());
+            });
+
+            // Spawn Fruit Name
+            let label = if unlocked {
+                strings.fruit_name(catalogue, &fruit.fruit_type())
+            } else {
+                strings.format(
+                    "village.unlock_level",
+                    &[("level", fruit.unlock_level.to_string())],
+                )
+            };
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 24.0,
+                    color: if unlocked { fruit.color() } else { Color::GRAY },
+                },
+            ));
         });
 }
 
-pub fn spawn_fruit(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_fruit_slots(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    theme: Res<Theme>,
+    game_settings: Res<GameSettings>,
+    catalogue: Res<FruitCatalogue>,
+    mut selection: ResMut<FruitSelection>,
+    input_map: Res<InputMap>,
+    strings: Strings,
+) {
+    selection.selected = None;
+
+    // Spawn Fruit A / Swap / Fruit B Row
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -486,289 +705,225 @@ pub fn spawn_fruit(mut commands: Commands, asset_server: Res<AssetServer>) {
                 justify_self: JustifySelf::Center,
                 // vertically center child text
                 align_self: AlignSelf::Center,
-                top: Val::Vh(-10.0),
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                top: Val::Px(58.0),
                 ..default()
             },
             ..default()
         })
         .with_children(|parent| {
             parent
-                .spawn((
-                    NodeBundle {
-                        style: Style {
-                            width: Val::Percent(100.),
-                            height: Val::Percent(100.),
-                            ..default()
-                        },
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
                         ..default()
                     },
-                    FruitType::Apple,
-                ))
+                    ..default()
+                })
                 .with_children(|parent| {
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: Style {
-                                    width: Val::Px(150.),
-                                    height: Val::Px(65.),
-                                    // horizontally center child text
-                                    justify_content: JustifyContent::Center,
-                                    // vertically center child text
-                                    align_items: AlignItems::Center,
-                                    // center the node vertically and horizontally within the window
-                                    position_type: PositionType::Relative,
-                                    border: UiRect {
-                                        top: Val::Px(4.),
-                                        left: Val::Px(4.),
-                                        bottom: Val::Px(4.),
-                                        right: Val::Px(4.),
-                                    },
-                                    margin: UiRect {
-                                        left: Val::Px(30.0),
-                                        right: Val::Px(30.0),
-                                        ..default()
-                                    },
-                                    ..default()
-                                },
-                                background_color: Color::BISQUE.into(),
-                                ..default()
-                            },
-                            FruitType::Apple,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                ImageBundle {
-                                    image: UiImage {
-                                        texture: asset_server.load("icons/apple.png"),
-                                        ..default()
-                                    },
-                                    ..default()
-                                },
-                                FruitType::Apple,
-                                Animator::new(
-                                    Tween::new(
-                                        EaseFunction::CubicInOut,
-                                        Duration::from_millis(500),
-                                        TransformRotationLens {
-                                            start: Quat::from_rotation_z(10_f32.to_radians()),
-                                            end: Quat::from_rotation_z(350_f32.to_radians()),
-                                        },
-                                    )
-                                    .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
-                                    .with_repeat_count(RepeatCount::Infinite),
-                                ),
-                            ));
-                        });
+                    slot_button(
+                        parent,
+                        &assets,
+                        &theme,
+                        &strings,
+                        &game_settings,
+                        &catalogue,
+                        FruitSlot::A,
+                    );
+                    ButtonBuilder::new(strings.get("village.swap"), SwapButton)
+                        .size(120., 50.)
+                        .font_size(32.)
+                        .spawn(parent, &theme);
+                    slot_button(
+                        parent,
+                        &assets,
+                        &theme,
+                        &strings,
+                        &game_settings,
+                        &catalogue,
+                        FruitSlot::B,
+                    );
                 });
 
-            parent
-                .spawn((
-                    NodeBundle {
-                        style: Style {
-                            // bottom right button
-                            width: Val::Percent(100.),
-                            height: Val::Percent(100.),
-                            ..default()
-                        },
-                        ..default()
+            // Spawn Slot Message
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 24.0,
+                        color: theme.emphasis,
                     },
-                    FruitType::Pear,
-                ))
+                ),
+                SlotMessage,
+                TextRole(ColorRole::Emphasis),
+            ));
+        });
+
+    // Spawn Shuffle Toggle
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                // top right button
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::End,
+                align_items: AlignItems::Start,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            ButtonBuilder::new(
+                shuffle_label(&strings, game_settings.shuffle),
+                ShuffleButton,
+            )
+            .size(250., 65.)
+            .spawn(parent, &theme);
+        });
+
+    // Spawn In Game Answer Button Preview
+    for slot in [FruitSlot::A, FruitSlot::B] {
+        answer_preview(
+            &mut commands,
+            &assets,
+            &theme,
+            &game_settings,
+            &catalogue,
+            &input_map,
+            slot,
+        );
+    }
+}
+
+fn slot_button(
+    builder: &mut ChildBuilder,
+    assets: &Res<GameAssets>,
+    theme: &Theme,
+    strings: &Strings,
+    game_settings: &Res<GameSettings>,
+    catalogue: &Res<FruitCatalogue>,
+    slot: FruitSlot,
+) {
+    let label = strings.get(match slot {
+        FruitSlot::A => "village.fruit_a",
+        FruitSlot::B => "village.fruit_b",
+    });
+
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                margin: UiRect::horizontal(Val::Px(20.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    label,
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 32.0,
+                        color: theme.text,
+                    },
+                ),
+                TextRole(ColorRole::Text),
+            ));
+            ButtonBuilder::empty(slot)
+                .border(slot_border(slot))
+                .spawn(parent, theme)
                 .with_children(|parent| {
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: Style {
-                                    width: Val::Px(150.),
-                                    height: Val::Px(65.),
-                                    // horizontally center child text
-                                    justify_content: JustifyContent::Center,
-                                    // vertically center child text
-                                    align_items: AlignItems::Center,
-                                    // center the node vertically and horizontally within the window
-                                    position_type: PositionType::Relative,
-                                    border: UiRect {
-                                        top: Val::Px(4.),
-                                        left: Val::Px(4.),
-                                        bottom: Val::Px(4.),
-                                        right: Val::Px(4.),
-                                    },
-                                    margin: UiRect {
-                                        left: Val::Px(30.0),
-                                        right: Val::Px(30.0),
-                                        ..default()
-                                    },
-                                    ..default()
-                                },
-                                background_color: Color::BISQUE.into(),
+                    parent.spawn((
+                        ImageBundle {
+                            image: UiImage {
+                                texture: assets
+                                    .fruit_icon(&catalogue.icon(game_settings.fruit(slot))),
                                 ..default()
                             },
-                            FruitType::Pear,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                ImageBundle {
-                                    image: UiImage {
-                                        texture: asset_server.load("icons/pear.png"),
-                                        ..default()
-                                    },
-                                    ..default()
-                                },
-                                FruitType::Pear,
-                                Animator::new(
-                                    Tween::new(
-                                        EaseFunction::CubicInOut,
-                                        Duration::from_millis(500),
-                                        TransformRotationLens {
-                                            start: Quat::from_rotation_z(10_f32.to_radians()),
-                                            end: Quat::from_rotation_z(350_f32.to_radians()),
-                                        },
-                                    )
-                                    .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
-                                    .with_repeat_count(RepeatCount::Infinite),
-                                ),
-                            ));
-                        });
+                            ..default()
+                        },
+                        SlotIcon(slot),
+                    ));
+                    spawn_fruit_mark(parent, theme, Some(slot));
                 });
+        });
+}
+
+// Mirrors the answer buttons from game_mod::setup_ui in the same corners
+fn answer_preview(
+    commands: &mut Commands,
+    assets: &Res<GameAssets>,
+    theme: &Theme,
+    game_settings: &Res<GameSettings>,
+    catalogue: &Res<FruitCatalogue>,
+    input_map: &Res<InputMap>,
+    slot: FruitSlot,
+) {
+    let (justify_content, key) = match slot {
+        FruitSlot::A => (JustifyContent::Start, input_map.key_name(Action::AnswerA)),
+        FruitSlot::B => (JustifyContent::End, input_map.key_name(Action::AnswerB)),
+    };
 
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content,
+                align_items: AlignItems::End,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
             parent
                 .spawn((
                     NodeBundle {
                         style: Style {
-                            // bottom left button
-                            width: Val::Percent(100.),
-                            height: Val::Percent(100.),
+                            width: Val::Px(150.),
+                            height: Val::Px(65.),
+                            // horizontally center child text
+                            justify_content: JustifyContent::SpaceEvenly,
+                            // vertically center child text
+                            align_items: AlignItems::Center,
+                            border: UiRect::all(Val::Px(4.)),
                             ..default()
                         },
+                        border_color: theme.accent.into(),
+                        background_color: theme.button.into(),
                         ..default()
                     },
-                    FruitType::Orange,
+                    BorderRole(ColorRole::Accent),
+                    FillRole(ColorRole::Button),
                 ))
                 .with_children(|parent| {
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: Style {
-                                    width: Val::Px(150.),
-                                    height: Val::Px(65.),
-                                    // horizontally center child text
-                                    justify_content: JustifyContent::Center,
-                                    // vertically center child text
-                                    align_items: AlignItems::Center,
-                                    // center the node vertically and horizontally within the window
-                                    position_type: PositionType::Relative,
-                                    border: UiRect {
-                                        top: Val::Px(4.),
-                                        left: Val::Px(4.),
-                                        bottom: Val::Px(4.),
-                                        right: Val::Px(4.),
-                                    },
-                                    margin: UiRect {
-                                        left: Val::Px(30.0),
-                                        right: Val::Px(30.0),
-                                        ..default()
-                                    },
-                                    ..default()
-                                },
-                                background_color: Color::BISQUE.into(),
+                    parent.spawn((
+                        ImageBundle {
+                            image: UiImage {
+                                texture: assets
+                                    .fruit_icon(&catalogue.icon(game_settings.fruit(slot))),
                                 ..default()
                             },
-                            FruitType::Orange,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                ImageBundle {
-                                    image: UiImage {
-                                        texture: asset_server.load("icons/orange.png"),
-                                        ..default()
-                                    },
-                                    ..default()
-                                },
-                                FruitType::Orange,
-                                Animator::new(
-                                    Tween::new(
-                                        EaseFunction::CubicInOut,
-                                        Duration::from_millis(500),
-                                        TransformRotationLens {
-                                            start: Quat::from_rotation_z(10_f32.to_radians()),
-                                            end: Quat::from_rotation_z(350_f32.to_radians()),
-                                        },
-                                    )
-                                    .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
-                                    .with_repeat_count(RepeatCount::Infinite),
-                                ),
-                            ));
-                        });
-                });
-            parent
-                .spawn((
-                    NodeBundle {
-                        style: Style {
-                            // bottom left button
-                            width: Val::Percent(100.),
-                            height: Val::Percent(100.),
                             ..default()
                         },
-                        ..default()
-                    },
-                    FruitType::Strawberry,
-                ))
-                .with_children(|parent| {
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: Style {
-                                    width: Val::Px(150.),
-                                    height: Val::Px(65.),
-                                    // horizontally center child text
-                                    justify_content: JustifyContent::Center,
-                                    // vertically center child text
-                                    align_items: AlignItems::Center,
-                                    // center the node vertically and horizontally within the window
-                                    position_type: PositionType::Relative,
-                                    margin: UiRect {
-                                        left: Val::Px(30.0),
-                                        right: Val::Px(30.0),
-                                        ..default()
-                                    },
-                                    border: UiRect {
-                                        top: Val::Px(4.),
-                                        left: Val::Px(4.),
-                                        bottom: Val::Px(4.),
-                                        right: Val::Px(4.),
-                                    },
-
-                                    ..default()
-                                },
-                                background_color: Color::BISQUE.into(),
-                                ..default()
+                        SlotIcon(slot),
+                    ));
+                    spawn_fruit_mark(parent, theme, Some(slot));
+                    parent.spawn((
+                        TextBundle::from_section(
+                            key,
+                            TextStyle {
+                                font: theme.font.clone(),
+                                font_size: 32.0,
+                                color: theme.text,
                             },
-                            FruitType::Strawberry,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                ImageBundle {
-                                    image: UiImage {
-                                        texture: asset_server.load("icons/strawberry.png"),
-                                        ..default()
-                                    },
-                                    ..default()
-                                },
-                                FruitType::Strawberry,
-                                Animator::new(
-                                    Tween::new(
-                                        EaseFunction::CubicInOut,
-                                        Duration::from_millis(500),
-                                        TransformRotationLens {
-                                            start: Quat::from_rotation_z(10_f32.to_radians()),
-                                            end: Quat::from_rotation_z(350_f32.to_radians()),
-                                        },
-                                    )
-                                    .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
-                                    .with_repeat_count(RepeatCount::Infinite),
-                                ),
-                            ));
-                        });
+                        ),
+                        TextRole(ColorRole::Text),
+                    ));
                 });
         });
 }
@@ -779,7 +934,6 @@ pub fn clear_shapes(
     mut query_title: Query<Entity, With<Text>>,
     mut query_stamps: Query<Entity, With<Sprite>>,
     mut query_fruits: Query<Entity, With<Node>>,
-    mut query_music: Query<Entity, (With<PlaybackSettings>, Without<SoundEffect>)>,
 ) {
     for entity in query.iter_mut() {
         if let Some(entity) = commands.get_entity(entity) {
@@ -801,66 +955,4 @@ pub fn clear_shapes(
             entity.despawn_recursive();
         }
     }
-    for entity in query_music.iter_mut() {
-        if let Some(entity) = commands.get_entity(entity) {
-            entity.despawn_recursive();
-        }
-    }
-}
-
-fn item_rect(
-    builder: &mut ChildBuilder,
-    asset_server: &Res<AssetServer>,
-    char_file: String,
-    unlocked: bool,
-    bounce_type: bool,
-) {
-    builder
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    display: Display::Grid,
-                    justify_self: JustifySelf::Center,
-                    align_self: AlignSelf::Center,
-                    ..default()
-                },
-                ..default()
-            },
-            GridIdentifier::Grid,
-        ))
-        .with_children(|builder| {
-            builder.spawn((
-                ImageBundle {
-                    style: Style {
-                        height: Val::Px(125.0),
-                        width: Val::Px(100.0),
-                        ..default()
-                    },
-                    image: asset_server.load(char_file).into(),
-                    background_color: (if unlocked {
-                        Color::WHITE.into()
-                    } else {
-                        Color::GRAY.into()
-                    }),
-                    ..default()
-                },
-                GridIdentifier::Grid,
-                Animator::new(
-                    Tween::new(
-                        if bounce_type {
-                            EaseFunction::BounceIn
-                        } else {
-                            EaseFunction::BounceOut
-                        },
-                        Duration::from_secs(1),
-                        UiPositionLens {
-                            start: UiRect::top(Val::Px(10.0)),
-                            end: UiRect::top(Val::Px(0.0)),
-                        },
-                    )
-                    .with_repeat_strategy(RepeatStrategy::MirroredRepeat)
-                    .with_repeat_count(RepeatCount::Infinite),
-                ),
-            ));
-        });
 }
@@ -0,0 +1,521 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::{
+    audio::{PlaySfx, SfxKind},
+    fruits::{FruitCatalogue, FruitType},
+    game_mod::GameData,
+    input::{Action, Actions},
+    layout::{self, PortraitStyle},
+    locale::Strings,
+    storage,
+    ui::{spawn_button, ColorRole, FillRole, TextRole, Theme},
+    AppState,
+};
+
+const STATS_SAVE: &str = "stats";
+// Rounds are grouped by how many more of one fruit there were, the last group
+// takes every bigger difference
+pub const DIFFERENCE_BUCKETS: usize = 5;
+// Reaction times are grouped in half seconds up to the round timer's five
+pub const REACTION_BUCKETS: usize = 10;
+const REACTION_BUCKET_SECS: f32 = 0.5;
+// Height of the tallest bar in either chart
+const CHART_HEIGHT: f32 = 200.0;
+
+/// Sent by game_mod as each round is answered or runs out of time
+#[derive(Event)]
+pub struct RoundFinished {
+    pub difference: u32,
+    pub correct: bool,
+    // None when the timer ran out
+    pub reaction: Option<Duration>,
+    pub fruit_a: FruitType,
+    pub fruit_b: FruitType,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Tally {
+    pub rounds: u32,
+    pub correct: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PairTally {
+    pub fruit_a: String,
+    pub fruit_b: String,
+    pub rounds: u32,
+}
+
+/// Every run the player has finished on this device
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerStats {
+    pub runs: u32,
+    pub levels_total: u32,
+    pub by_difference: [Tally; DIFFERENCE_BUCKETS],
+    pub reactions: [u32; REACTION_BUCKETS],
+    pub pairs: Vec<PairTally>,
+}
+
+impl PlayerStats {
+    pub fn save(&self) {
+        storage::save(STATS_SAVE, self);
+    }
+
+    pub fn average_level(&self) -> Option<f32> {
+        (self.runs > 0).then(|| self.levels_total as f32 / self.runs as f32)
+    }
+
+    pub fn favourite_pair(&self) -> Option<&PairTally> {
+        self.pairs.iter().max_by_key(|pair| pair.rounds)
+    }
+
+    fn record_round(&mut self, round: &RoundFinished) {
+        // Rounds are never tied, so the difference is at least one
+        let bucket = (round.difference.max(1) as usize).min(DIFFERENCE_BUCKETS) - 1;
+        self.by_difference[bucket].rounds += 1;
+        if round.correct {
+            self.by_difference[bucket].correct += 1;
+        }
+
+        if let Some(reaction) = round.reaction {
+            let bucket = (reaction.as_secs_f32() / REACTION_BUCKET_SECS) as usize;
+            self.reactions[bucket.min(REACTION_BUCKETS - 1)] += 1;
+        }
+
+        // Pairs are kept in id order so swapping the slots still counts as the same pair
+        let (fruit_a, fruit_b) = if round.fruit_a.0 <= round.fruit_b.0 {
+            (&round.fruit_a.0, &round.fruit_b.0)
+        } else {
+            (&round.fruit_b.0, &round.fruit_a.0)
+        };
+        let pair = self
+            .pairs
+            .iter_mut()
+            .find(|pair| &pair.fruit_a == fruit_a && &pair.fruit_b == fruit_b);
+        match pair {
+            Some(pair) => pair.rounds += 1,
+            None => self.pairs.push(PairTally {
+                fruit_a: fruit_a.clone(),
+                fruit_b: fruit_b.clone(),
+                rounds: 1,
+            }),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct StatsScreen;
+
+#[derive(Component)]
+pub enum BackButton {
+    MainMenu,
+}
+
+pub fn load_player_stats(mut stats: ResMut<PlayerStats>) {
+    if let Some(saved) = storage::load::<PlayerStats>(STATS_SAVE) {
+        *stats = saved;
+    }
+}
+
+pub fn record_rounds(mut rounds: EventReader<RoundFinished>, mut stats: ResMut<PlayerStats>) {
+    let mut recorded = false;
+    for round in rounds.read() {
+        stats.record_round(round);
+        recorded = true;
+    }
+    if recorded {
+        stats.save();
+    }
+}
+
+// Every run ends in GameOver, restarted ones included, after game_over has filled in its level
+pub fn record_run(game_data: Res<GameData>, mut stats: ResMut<PlayerStats>) {
+    stats.runs += 1;
+    stats.levels_total += game_data.run().level.max(1) as u32;
+    stats.save();
+}
+
+pub fn setup_ui(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    stats: Res<PlayerStats>,
+    catalogue: Res<FruitCatalogue>,
+    strings: Strings,
+) {
+    // Spawn Title Text
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                strings.get("stats.title"),
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 64.0,
+                    color: theme.text,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            style: Style {
+                top: Val::Px(-288.0),
+                justify_self: JustifySelf::Center,
+                align_self: AlignSelf::Center,
+                ..default()
+            },
+            ..default()
+        },
+        StatsScreen,
+        TextRole(ColorRole::Text),
+    ));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // horizontally center child text
+                    justify_self: JustifySelf::Center,
+                    // vertically center child text
+                    align_self: AlignSelf::Center,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    top: Val::Px(30.0),
+                    ..default()
+                },
+                ..default()
+            },
+            StatsScreen,
+            // The charts stack in portrait, move down to clear the title
+            PortraitStyle::new(|style| style.top = Val::Px(120.0)),
+        ))
+        .with_children(|parent| {
+            if stats.runs == 0 {
+                parent.spawn((
+                    TextBundle::from_section(strings.get("stats.empty"), theme.text_style(32.0)),
+                    TextRole(ColorRole::Text),
+                ));
+                return;
+            }
+
+            // Spawn Summary
+            let average_level = stats
+                .average_level()
+                .map_or_else(|| "-".to_string(), |level| format!("{:.1}", level));
+            let favourite_pair = stats.favourite_pair().map_or_else(
+                || "-".to_string(),
+                |pair| {
+                    strings.format(
+                        "stats.pair",
+                        &[
+                            (
+                                "fruit_a",
+                                strings.fruit_name(&catalogue, &FruitType::new(&pair.fruit_a)),
+                            ),
+                            (
+                                "fruit_b",
+                                strings.fruit_name(&catalogue, &FruitType::new(&pair.fruit_b)),
+                            ),
+                        ],
+                    )
+                },
+            );
+            for (name_key, value) in [
+                ("stats.runs", stats.runs.to_string()),
+                ("stats.average_level", average_level),
+                ("stats.favourite_pair", favourite_pair),
+            ] {
+                parent.spawn((
+                    TextBundle::from_section(
+                        strings.setting(name_key, value),
+                        theme.text_style(32.0),
+                    ),
+                    TextRole(ColorRole::Text),
+                ));
+            }
+
+            // Spawn Charts
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            column_gap: Val::Px(80.),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    PortraitStyle::new(layout::stack_in_column),
+                ))
+                .with_children(|parent| {
+                    let accuracy: Vec<(String, f32, String)> = stats
+                        .by_difference
+                        .iter()
+                        .enumerate()
+                        .map(|(index, tally)| {
+                            let label = if index + 1 == DIFFERENCE_BUCKETS {
+                                format!("{}+", index + 1)
+                            } else {
+                                (index + 1).to_string()
+                            };
+                            if tally.rounds == 0 {
+                                return (label, 0.0, "-".to_string());
+                            }
+                            let accuracy = tally.correct as f32 / tally.rounds as f32;
+                            (label, accuracy, format!("{:.0}%", accuracy * 100.))
+                        })
+                        .collect();
+                    bar_chart(
+                        parent,
+                        &theme,
+                        strings.get("stats.accuracy_by_difference"),
+                        ColorRole::Accent,
+                        &accuracy,
+                    );
+
+                    let most = stats.reactions.iter().copied().max().unwrap_or(0).max(1);
+                    let reactions: Vec<(String, f32, String)> = stats
+                        .reactions
+                        .iter()
+                        .enumerate()
+                        .map(|(index, count)| {
+                            (
+                                format!("{}", (index + 1) as f32 * REACTION_BUCKET_SECS),
+                                *count as f32 / most as f32,
+                                count.to_string(),
+                            )
+                        })
+                        .collect();
+                    bar_chart(
+                        parent,
+                        &theme,
+                        strings.get("stats.reaction_times"),
+                        ColorRole::Highlight,
+                        &reactions,
+                    );
+                });
+        });
+
+    // Spawn Menu Button
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::Start,
+                    ..default()
+                },
+                ..default()
+            },
+            BackButton::MainMenu,
+        ))
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                &theme,
+                strings.get("common.back"),
+                BackButton::MainMenu,
+            );
+        });
+}
+
+// Titled row of bars, each with its value above and its label below, heights from 0 to 1
+fn bar_chart(
+    builder: &mut ChildBuilder,
+    theme: &Theme,
+    title: String,
+    role: ColorRole,
+    bars: &[(String, f32, String)],
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    title,
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 32.0,
+                        color: theme.emphasis,
+                    },
+                ),
+                TextRole(ColorRole::Emphasis),
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::End,
+                        column_gap: Val::Px(8.),
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (label, height, value) in bars {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Px(40.),
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_section(value.clone(), theme.text_style(18.0)),
+                                    TextRole(ColorRole::Text),
+                                ));
+                                parent.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            width: Val::Px(28.),
+                                            height: Val::Px(height.clamp(0.0, 1.0) * CHART_HEIGHT),
+                                            ..default()
+                                        },
+                                        background_color: role.color(theme).into(),
+                                        ..default()
+                                    },
+                                    FillRole(role),
+                                ));
+                                parent.spawn((
+                                    TextBundle::from_section(label.clone(), theme.text_style(20.0)),
+                                    TextRole(ColorRole::Text),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+pub fn interact_button(
+    interaction_query: Query<(&Interaction, &BackButton), (Changed<Interaction>, With<Button>)>,
+    mut next_state: ResMut<NextState<AppState>>,
+    actions: Actions,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    if actions.just_released(Action::Back) {
+        sfx.send(PlaySfx(SfxKind::Back));
+        next_state.set(AppState::MainMenu);
+    }
+    // Buttons
+    for (interaction, back_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            match back_button {
+                BackButton::MainMenu => next_state.set(AppState::MainMenu),
+            }
+        }
+    }
+}
+
+pub fn clear_shapes(
+    mut commands: Commands,
+    mut query: Query<Entity, Or<(With<BackButton>, With<StatsScreen>)>>,
+) {
+    for entity in query.iter_mut() {
+        if let Some(entity) = commands.get_entity(entity) {
+            entity.despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(difference: u32, correct: bool, reaction: Option<f32>) -> RoundFinished {
+        RoundFinished {
+            difference,
+            correct,
+            reaction: reaction.map(Duration::from_secs_f32),
+            fruit_a: FruitType::new("apple"),
+            fruit_b: FruitType::new("pear"),
+        }
+    }
+
+    #[test]
+    fn difference_of_zero_lands_in_the_first_bucket() {
+        let mut stats = PlayerStats::default();
+        stats.record_round(&round(0, true, None));
+        assert_eq!(stats.by_difference[0].rounds, 1);
+        assert_eq!(stats.by_difference[0].correct, 1);
+    }
+
+    #[test]
+    fn large_differences_share_the_last_bucket() {
+        let mut stats = PlayerStats::default();
+        stats.record_round(&round(5, true, None));
+        stats.record_round(&round(12, false, None));
+        let last = stats.by_difference[DIFFERENCE_BUCKETS - 1];
+        assert_eq!((last.rounds, last.correct), (2, 1));
+        assert_eq!(stats.by_difference[3].rounds, 0);
+    }
+
+    #[test]
+    fn reactions_are_bucketed_by_half_second() {
+        let mut stats = PlayerStats::default();
+        stats.record_round(&round(1, true, Some(0.2)));
+        stats.record_round(&round(1, true, Some(0.5)));
+        stats.record_round(&round(1, true, Some(1.2)));
+        assert_eq!(stats.reactions[0], 1);
+        assert_eq!(stats.reactions[1], 1);
+        assert_eq!(stats.reactions[2], 1);
+    }
+
+    #[test]
+    fn slow_reactions_are_clamped_to_the_last_bucket() {
+        let mut stats = PlayerStats::default();
+        stats.record_round(&round(1, true, Some(4.9)));
+        stats.record_round(&round(1, true, Some(30.0)));
+        assert_eq!(stats.reactions[REACTION_BUCKETS - 1], 2);
+    }
+
+    #[test]
+    fn timeouts_count_towards_accuracy_but_not_reactions() {
+        let mut stats = PlayerStats::default();
+        stats.record_round(&round(2, false, None));
+        assert_eq!(stats.by_difference[1].rounds, 1);
+        assert_eq!(stats.reactions.iter().sum::<u32>(), 0);
+    }
+
+    #[test]
+    fn favourite_pair_is_the_most_played() {
+        let mut stats = PlayerStats::default();
+        stats.record_round(&round(1, true, None));
+        let mut other = round(1, true, None);
+        other.fruit_b = FruitType::new("banana");
+        stats.record_round(&other);
+        stats.record_round(&other);
+        let favourite = stats.favourite_pair().unwrap();
+        assert_eq!(favourite.fruit_b, "banana");
+        assert_eq!(favourite.rounds, 2);
+        assert_eq!(stats.pairs.len(), 2);
+    }
+
+    #[test]
+    fn swapped_fruit_count_as_the_same_pair() {
+        let mut stats = PlayerStats::default();
+        stats.record_round(&round(1, true, None));
+        let mut swapped = round(1, true, None);
+        swapped.fruit_a = FruitType::new("pear");
+        swapped.fruit_b = FruitType::new("apple");
+        stats.record_round(&swapped);
+        assert_eq!(stats.pairs.len(), 1);
+        let favourite = stats.favourite_pair().unwrap();
+        assert_eq!(
+            (favourite.fruit_a.as_str(), favourite.fruit_b.as_str()),
+            ("apple", "pear")
+        );
+        assert_eq!(favourite.rounds, 2);
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::log::warn;
+use serde::{de::DeserializeOwned, Serialize};
+
+// Saves live next to the game on desktop and in localStorage on the web
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_DIR: &str = "saves";
+#[cfg(target_arch = "wasm32")]
+const STORAGE_PREFIX: &str = "mode.";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let json = match serde_json::to_string_pretty(value) {
+        Ok(json) => json,
+        Err(error) => {
+            warn!("Could not serialize {}: {}", name, error);
+            return;
+        }
+    };
+    if let Err(error) = std::fs::create_dir_all(SAVE_DIR)
+        .and_then(|_| std::fs::write(format!("{}/{}.json", SAVE_DIR, name), json))
+    {
+        warn!("Could not save {}: {}", name, error);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let json = std::fs::read_to_string(format!("{}/{}.json", SAVE_DIR, name)).ok()?;
+    match serde_json::from_str(&json) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Ignoring unreadable save {}: {}", name, error);
+            None
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let Ok(json) = serde_json::to_string(value) else {
+        warn!("Could not serialize {}", name);
+        return;
+    };
+    if let Some(storage) = local_storage() {
+        if storage
+            .set_item(&format!("{}{}", STORAGE_PREFIX, name), &json)
+            .is_err()
+        {
+            warn!("Could not save {}", name);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let json = local_storage()?
+        .get_item(&format!("{}{}", STORAGE_PREFIX, name))
+        .ok()??;
+    serde_json::from_str(&json).ok()
+}
@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+
+use crate::{game_mod::AnswerButton, layout::ScreenLayout};
+
+// How long a finger has to stay down for a long press
+const LONG_PRESS_SECS: f32 = 0.5;
+
+/// Set once the player touches the screen, cleared again by the mouse
+#[derive(Resource, Default, PartialEq)]
+pub struct TouchMode {
+    pub active: bool,
+}
+
+/// Tracks a finger held on one button, standing in for a right click
+#[derive(Resource)]
+pub struct LongPress {
+    entity: Option<Entity>,
+    timer: Timer,
+    fired: bool,
+}
+
+impl Default for LongPress {
+    fn default() -> Self {
+        Self {
+            entity: None,
+            timer: Timer::from_seconds(LONG_PRESS_SECS, TimerMode::Once),
+            fired: false,
+        }
+    }
+}
+
+impl LongPress {
+    /// Keeps the press on `entity` going, true on the frame it becomes a long press
+    pub fn hold(&mut self, entity: Entity, delta: std::time::Duration) -> bool {
+        if self.entity != Some(entity) {
+            self.entity = Some(entity);
+            self.timer.reset();
+            self.fired = false;
+        }
+        self.timer.tick(delta);
+        if self.timer.finished() && !self.fired {
+            self.fired = true;
+            return true;
+        }
+        false
+    }
+
+    /// Ends the press, handing back the entity if it was only a tap
+    pub fn release(&mut self) -> Option<Entity> {
+        let entity = self.entity.take();
+        if self.fired {
+            None
+        } else {
+            entity
+        }
+    }
+}
+
+pub fn detect_touch(
+    touches: Res<Touches>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut touch_mode: ResMut<TouchMode>,
+) {
+    if touches.any_just_pressed() {
+        touch_mode.set_if_neq(TouchMode { active: true });
+    } else if mouse_input.get_just_pressed().next().is_some() {
+        touch_mode.set_if_neq(TouchMode { active: false });
+    }
+}
+
+pub fn resize_answer_zones(
+    touch_mode: Res<TouchMode>,
+    layout: Res<ScreenLayout>,
+    mut answer_query: Query<&mut Style, (With<AnswerButton>, With<Button>)>,
+) {
+    // Touch turns each answer into half the bottom of the screen
+    let (width, height) = match (touch_mode.active, layout.portrait) {
+        (false, _) => (Val::Px(150.), Val::Px(65.)),
+        (true, false) => (Val::Percent(50.), Val::Percent(35.)),
+        (true, true) => (Val::Percent(50.), Val::Percent(25.)),
+    };
+    for mut style in answer_query.iter_mut() {
+        if style.width != width || style.height != height {
+            style.width = width;
+            style.height = height;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const FRAME: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn quick_release_is_a_tap() {
+        let mut long_press = LongPress::default();
+        let entity = Entity::from_raw(1);
+        assert!(!long_press.hold(entity, FRAME));
+        assert_eq!(long_press.release(), Some(entity));
+    }
+
+    #[test]
+    fn holding_fires_once() {
+        let mut long_press = LongPress::default();
+        let entity = Entity::from_raw(1);
+        let held = Duration::from_secs_f32(LONG_PRESS_SECS);
+        assert!(long_press.hold(entity, held));
+        assert!(!long_press.hold(entity, FRAME));
+        assert_eq!(long_press.release(), None);
+    }
+
+    #[test]
+    fn moving_to_another_entity_starts_over() {
+        let mut long_press = LongPress::default();
+        let first = Entity::from_raw(1);
+        let second = Entity::from_raw(2);
+        let almost = Duration::from_secs_f32(LONG_PRESS_SECS * 0.9);
+        assert!(!long_press.hold(first, almost));
+        assert!(!long_press.hold(second, almost));
+        assert_eq!(long_press.release(), Some(second));
+    }
+
+    #[test]
+    fn release_resets_for_the_next_press() {
+        let mut long_press = LongPress::default();
+        let entity = Entity::from_raw(1);
+        long_press.hold(entity, Duration::from_secs_f32(LONG_PRESS_SECS));
+        long_press.release();
+        assert!(!long_press.hold(entity, FRAME));
+        assert_eq!(long_press.release(), Some(entity));
+    }
+}
@@ -0,0 +1,442 @@
+use bevy::{core_pipeline::clear_color::ClearColorConfig, ecs::system::EntityCommands, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::GameAssets,
+    audio::{PlaySfx, SfxKind},
+    focus::Focused,
+    locale::Strings,
+    storage,
+};
+
+pub const OLIVE_GREEN: Color = Color::rgb(82.0 / 255.0, 88.0 / 255.0, 32.0 / 255.0);
+pub const BASIL_GREEN: Color = Color::rgb(166.0 / 255.0, 179.0 / 255.0, 64.0 / 255.0);
+pub const SKY_BLUE: Color = Color::rgb(137.0 / 255.0, 204.0 / 255.0, 196.0 / 255.0);
+
+const THEME_SAVE: &str = "theme";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+    #[default]
+    Day,
+    Night,
+    HighContrast,
+    // Okabe-Ito colours, Fruit A and Fruit B stay apart for red-green
+    // and blue-yellow colour blindness
+    Colorblind,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [
+        Palette::Day,
+        Palette::Night,
+        Palette::HighContrast,
+        Palette::Colorblind,
+    ];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Palette::Day => "palette.day",
+            Palette::Night => "palette.night",
+            Palette::HighContrast => "palette.high_contrast",
+            Palette::Colorblind => "palette.colorblind",
+        }
+    }
+
+    pub fn next(&self) -> Palette {
+        let index = Palette::ALL
+            .iter()
+            .position(|palette| palette == self)
+            .unwrap_or(0);
+        Palette::ALL[(index + 1) % Palette::ALL.len()]
+    }
+}
+
+/// Colours and font shared by every screen's widgets
+#[derive(Resource, Clone)]
+pub struct Theme {
+    pub palette: Palette,
+    pub text: Color,
+    pub accent: Color,
+    pub highlight: Color,
+    pub button: Color,
+    pub background: Color,
+    // Hovered borders, progress fills and text that should stand out
+    pub emphasis: Color,
+    pub pressed: Color,
+    pub error: Color,
+    // Multiplied into the scenery behind the menus
+    pub tint: Color,
+    pub font: Handle<Font>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(Palette::Day, Handle::default())
+    }
+}
+
+impl Theme {
+    pub fn new(palette: Palette, font: Handle<Font>) -> Self {
+        match palette {
+            Palette::Day => Self {
+                palette,
+                text: OLIVE_GREEN,
+                accent: BASIL_GREEN,
+                highlight: SKY_BLUE,
+                button: Color::BISQUE,
+                background: Color::BISQUE,
+                emphasis: Color::SALMON,
+                pressed: Color::WHITE,
+                error: Color::CRIMSON,
+                tint: Color::WHITE,
+                font,
+            },
+            Palette::Night => Self {
+                palette,
+                text: Color::rgb(0.91, 0.93, 0.97),
+                accent: Color::rgb(0.55, 0.78, 0.62),
+                highlight: Color::rgb(0.55, 0.75, 0.95),
+                button: Color::rgb(0.2, 0.24, 0.34),
+                background: Color::rgb(0.11, 0.14, 0.22),
+                emphasis: Color::rgb(0.98, 0.72, 0.55),
+                pressed: Color::rgb(1.0, 1.0, 0.8),
+                error: Color::rgb(1.0, 0.45, 0.45),
+                tint: Color::rgb(0.45, 0.5, 0.75),
+                font,
+            },
+            Palette::HighContrast => Self {
+                palette,
+                text: Color::WHITE,
+                accent: Color::YELLOW,
+                highlight: Color::CYAN,
+                button: Color::rgb(0.1, 0.1, 0.1),
+                background: Color::BLACK,
+                emphasis: Color::ORANGE,
+                pressed: Color::LIME_GREEN,
+                error: Color::rgb(1.0, 0.3, 0.3),
+                tint: Color::rgb(0.35, 0.35, 0.35),
+                font,
+            },
+            Palette::Colorblind => Self {
+                palette,
+                text: Color::rgb_u8(30, 30, 30),
+                accent: Color::rgb_u8(0, 114, 178),
+                highlight: Color::rgb_u8(230, 159, 0),
+                button: Color::rgb_u8(250, 246, 235),
+                background: Color::rgb_u8(238, 232, 218),
+                emphasis: Color::rgb_u8(204, 121, 167),
+                pressed: Color::rgb_u8(86, 180, 233),
+                error: Color::rgb_u8(213, 94, 0),
+                tint: Color::WHITE,
+                font,
+            },
+        }
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        *self = Theme::new(palette, self.font.clone());
+    }
+
+    pub fn save(&self) {
+        storage::save(THEME_SAVE, &self.palette);
+    }
+
+    pub fn text_style(&self, font_size: f32) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size,
+            color: self.text,
+        }
+    }
+}
+
+/// Which of the theme's colours a widget was painted with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorRole {
+    Text,
+    Accent,
+    Highlight,
+    Button,
+    Emphasis,
+    Error,
+}
+
+impl ColorRole {
+    pub fn color(&self, theme: &Theme) -> Color {
+        match self {
+            ColorRole::Text => theme.text,
+            ColorRole::Accent => theme.accent,
+            ColorRole::Highlight => theme.highlight,
+            ColorRole::Button => theme.button,
+            ColorRole::Emphasis => theme.emphasis,
+            ColorRole::Error => theme.error,
+        }
+    }
+}
+
+// Tags for `apply_theme`, widgets without one keep their colours when the palette changes
+
+/// Colour of every section of the entity's `Text`
+#[derive(Component, Clone, Copy)]
+pub struct TextRole(pub ColorRole);
+
+/// Colour of each section of the entity's `Text`, for text that mixes roles
+#[derive(Component, Clone)]
+pub struct SectionRoles(pub Vec<ColorRole>);
+
+/// Colour of the entity's `BackgroundColor`
+#[derive(Component, Clone, Copy)]
+pub struct FillRole(pub ColorRole);
+
+/// Colour of the entity's `BorderColor`, themed buttons work theirs out from `ThemedButton`
+#[derive(Component, Clone, Copy)]
+pub struct BorderRole(pub ColorRole);
+
+/// Background artwork tinted to match the palette
+#[derive(Component)]
+pub struct Scenery;
+
+/// Colour a themed button's border rests at when it isn't hovered or pressed
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum ButtonBorder {
+    #[default]
+    Text,
+    Accent,
+    Highlight,
+}
+
+impl ButtonBorder {
+    pub fn color(&self, theme: &Theme) -> Color {
+        match self {
+            ButtonBorder::Text => theme.text,
+            ButtonBorder::Accent => theme.accent,
+            ButtonBorder::Highlight => theme.highlight,
+        }
+    }
+}
+
+/// Button whose border and click sound are handled by `interact_buttons`
+#[derive(Component, Clone, Copy)]
+pub struct ThemedButton {
+    pub border: ButtonBorder,
+    pub click: SfxKind,
+}
+
+impl Default for ThemedButton {
+    fn default() -> Self {
+        Self {
+            border: ButtonBorder::Text,
+            click: SfxKind::Select,
+        }
+    }
+}
+
+/// Labelled menu button, 150x65 with a 40pt label unless told otherwise
+pub struct ButtonBuilder<A: Bundle> {
+    label: Option<String>,
+    action: A,
+    width: f32,
+    height: f32,
+    font_size: f32,
+    margin: UiRect,
+    themed: ThemedButton,
+}
+
+impl<A: Bundle> ButtonBuilder<A> {
+    pub fn new(label: impl Into<String>, action: A) -> Self {
+        Self {
+            label: Some(label.into()),
+            action,
+            width: 150.0,
+            height: 65.0,
+            font_size: 40.0,
+            margin: UiRect::default(),
+            themed: ThemedButton::default(),
+        }
+    }
+
+    /// Button without a label, for callers that fill it with their own children
+    pub fn empty(action: A) -> Self {
+        Self {
+            label: None,
+            ..Self::new("", action)
+        }
+    }
+
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    pub fn margin(mut self, margin: UiRect) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn border(mut self, border: ButtonBorder) -> Self {
+        self.themed.border = border;
+        self
+    }
+
+    pub fn click(mut self, click: SfxKind) -> Self {
+        self.themed.click = click;
+        self
+    }
+
+    pub fn spawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        theme: &Theme,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut button = parent.spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(self.width),
+                    height: Val::Px(self.height),
+                    // horizontally center child text
+                    justify_content: JustifyContent::Center,
+                    // vertically center child text
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(4.)),
+                    margin: self.margin,
+                    ..default()
+                },
+                border_color: self.themed.border.color(theme).into(),
+                background_color: theme.button.into(),
+                ..default()
+            },
+            FillRole(ColorRole::Button),
+            self.themed,
+            self.action,
+        ));
+        if let Some(label) = self.label {
+            button.with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_section(label, theme.text_style(self.font_size)),
+                    TextRole(ColorRole::Text),
+                ));
+            });
+        }
+        button
+    }
+}
+
+pub fn spawn_button<'w, 's, 'a, A: Bundle>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &Theme,
+    label: impl Into<String>,
+    action: A,
+) -> EntityCommands<'w, 's, 'a> {
+    ButtonBuilder::new(label, action).spawn(parent, theme)
+}
+
+pub fn load_theme(assets: Res<GameAssets>, mut theme: ResMut<Theme>) {
+    let palette = storage::load::<Palette>(THEME_SAVE).unwrap_or_default();
+    *theme = Theme::new(palette, assets.font.clone());
+}
+
+// Switch to the current language's font once its locale has loaded
+pub fn follow_language_font(strings: Strings, mut theme: ResMut<Theme>) {
+    let font = strings.font();
+    if theme.font != font {
+        theme.font = font;
+    }
+}
+
+// Recolour whatever is on screen when the palette changes, later screens
+// pick the new colours up as they spawn
+pub fn apply_theme(
+    theme: Res<Theme>,
+    mut previous: Local<Option<Theme>>,
+    mut text_query: Query<(&mut Text, Option<&TextRole>, Option<&SectionRoles>)>,
+    mut fill_query: Query<(&mut BackgroundColor, &FillRole)>,
+    mut border_query: Query<(&mut BorderColor, &BorderRole), Without<ThemedButton>>,
+    mut button_query: Query<(&mut BorderColor, &ThemedButton, &Interaction, Has<Focused>)>,
+    mut camera_query: Query<&mut Camera2d>,
+    mut scenery_query: Query<&mut TextureAtlasSprite, With<Scenery>>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    // A new language can bring its own font
+    if let Some(old) = previous.as_ref().filter(|old| old.font != theme.font) {
+        for (mut text, ..) in text_query.iter_mut() {
+            for section in text.sections.iter_mut() {
+                if section.style.font == old.font {
+                    section.style.font = theme.font.clone();
+                }
+            }
+        }
+    }
+    if previous
+        .as_ref()
+        .is_some_and(|old| old.palette != theme.palette)
+    {
+        for (mut text, role, section_roles) in text_query.iter_mut() {
+            for (index, section) in text.sections.iter_mut().enumerate() {
+                let role = match (role, section_roles) {
+                    (Some(role), _) => role.0,
+                    (None, Some(roles)) => match roles.0.get(index) {
+                        Some(role) => *role,
+                        None => continue,
+                    },
+                    (None, None) => break,
+                };
+                section.style.color = role.color(&theme);
+            }
+        }
+        for (mut background, role) in fill_query.iter_mut() {
+            background.0 = role.0.color(&theme);
+        }
+        for (mut border, role) in border_query.iter_mut() {
+            border.0 = role.0.color(&theme);
+        }
+        // Same as interact_buttons and draw_focus would paint them
+        for (mut border, themed, interaction, focused) in button_query.iter_mut() {
+            border.0 = match *interaction {
+                Interaction::Pressed => theme.pressed,
+                Interaction::Hovered => theme.emphasis,
+                Interaction::None if focused => theme.emphasis,
+                Interaction::None => themed.border.color(&theme),
+            };
+        }
+    }
+    for mut camera in camera_query.iter_mut() {
+        camera.clear_color = ClearColorConfig::Custom(theme.background);
+    }
+    for mut sprite in scenery_query.iter_mut() {
+        sprite.color = theme.tint;
+    }
+    *previous = Some(theme.clone());
+}
+
+pub fn interact_buttons(
+    theme: Res<Theme>,
+    mut interaction_query: Query<
+        (&Interaction, &ThemedButton, &mut BorderColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    for (interaction, themed, mut border_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                sfx.send(PlaySfx(themed.click));
+                *border_color = theme.pressed.into();
+            }
+            Interaction::Hovered => {
+                *border_color = theme.emphasis.into();
+            }
+            Interaction::None => {
+                *border_color = themed.border.color(&theme).into();
+            }
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use rand::Rng;
+
+use crate::{
+    accessibility::AccessibilitySettings,
+    assets::GameAssets,
+    loading::AnimationIndices,
+    locale::Strings,
+    settings::Villagers,
+    ui::{ColorRole, TextRole, Theme},
+};
+
+// Area of the background the villagers are allowed to walk around in
+const GROUND_MIN: Vec2 = Vec2::new(-560.0, -320.0);
+const GROUND_MAX: Vec2 = Vec2::new(560.0, -230.0);
+const VILLAGER_SCALE: f32 = 1.5;
+const WALK_SPEED: f32 = 60.0;
+
+// Every walk sheet is one row of frames this size, the villager stands on the bottom edge
+const WALK_FRAME_SIZE: Vec2 = Vec2::new(56.0, 82.0);
+const WALK_FRAMES: usize = 4;
+
+#[derive(Component)]
+pub struct Villager {
+    pub index: usize,
+    pub state: VillagerState,
+    pub position: Vec2,
+    pub target: Vec2,
+}
+
+pub enum VillagerState {
+    Idle(Timer),
+    Walking,
+    Talking(Timer),
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct AnimationTimer(Timer);
+
+#[derive(Component)]
+pub struct SpeechBubble;
+
+pub fn spawn_villagers(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    theme: Res<Theme>,
+    strings: Strings,
+    villagers: Res<Villagers>,
+) {
+    let mut rng = rand::thread_rng();
+    let unlocked = villagers.villagers.iter().filter(|v| v.2).count();
+
+    // Spawn Villager Count
+    let title = if unlocked == 0 {
+        strings.get("village.locked")
+    } else {
+        strings.format(
+            "village.unlocked",
+            &[
+                ("count", unlocked.to_string()),
+                ("total", villagers.villagers.len().to_string()),
+            ],
+        )
+    };
+
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                title,
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 32.0,
+                    color: theme.text,
+                },
+            )
+            .with_alignment(TextAlignment::Center),
+            style: Style {
+                top: Val::Px(158.0),
+                justify_self: JustifySelf::Center,
+                align_self: AlignSelf::Center,
+                ..default()
+            },
+            ..default()
+        },
+        TextRole(ColorRole::Text),
+    ));
+
+    // Spawn Unlocked Villagers
+    for (index, villager) in villagers.villagers.iter().enumerate() {
+        if !villager.2 {
+            continue;
+        }
+        let position = random_ground_point(&mut rng);
+        let texture_atlas = TextureAtlas::from_grid(
+            assets.character(&walk_sheet_path(&villager.1)),
+            WALK_FRAME_SIZE,
+            WALK_FRAMES,
+            1,
+            None,
+            None,
+        );
+        let animation_indices = AnimationIndices {
+            first: 0,
+            last: WALK_FRAMES - 1,
+        };
+        commands.spawn((
+            SpriteSheetBundle {
+                texture_atlas: texture_atlases.add(texture_atlas),
+                sprite: TextureAtlasSprite::new(animation_indices.first),
+                transform: Transform::from_translation(position.extend(villager_depth(position)))
+                    .with_scale(Vec3::splat(VILLAGER_SCALE)),
+                ..default()
+            },
+            Villager {
+                index,
+                state: VillagerState::Idle(Timer::from_seconds(
+                    rng.gen_range(0.5..3.0),
+                    TimerMode::Once,
+                )),
+                position,
+                target: position,
+            },
+            animation_indices,
+            AnimationTimer(Timer::from_seconds(0.15, TimerMode::Repeating)),
+        ));
+    }
+}
+
+pub fn wander_villagers(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut Villager,
+        &AnimationIndices,
+        &mut AnimationTimer,
+        &mut Transform,
+        &mut TextureAtlasSprite,
+    )>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (mut villager, indices, mut timer, mut transform, mut sprite) in query.iter_mut() {
+        let mut walking = false;
+
+        match villager.state {
+            VillagerState::Idle(ref mut timer) => {
+                timer.tick(time.delta());
+                if timer.finished() {
+                    villager.target = random_ground_point(&mut rng);
+                    villager.state = VillagerState::Walking;
+                }
+            }
+            VillagerState::Walking => {
+                let to_target = villager.target - villager.position;
+                let step = WALK_SPEED * time.delta_seconds();
+                if to_target.length() <= step {
+                    villager.position = villager.target;
+                    villager.state = VillagerState::Idle(Timer::from_seconds(
+                        rng.gen_range(1.0..4.0),
+                        TimerMode::Once,
+                    ));
+                } else {
+                    villager.position += to_target.normalize() * step;
+                    sprite.flip_x = to_target.x < 0.0;
+                    walking = true;
+                }
+            }
+            VillagerState::Talking(ref mut timer) => {
+                timer.tick(time.delta());
+                if timer.finished() {
+                    villager.state = VillagerState::Idle(Timer::from_seconds(1.0, TimerMode::Once));
+                }
+            }
+        }
+
+        // Step through the walk cycle while moving, rest on the first frame otherwise
+        if walking {
+            timer.tick(time.delta());
+            if timer.just_finished() {
+                sprite.index = if sprite.index == indices.last {
+                    indices.first
+                } else {
+                    sprite.index + 1
+                };
+            }
+        } else {
+            sprite.index = indices.first;
+            timer.reset();
+        }
+
+        transform.translation = villager.position.extend(villager_depth(villager.position));
+    }
+}
+
+pub fn click_villagers(
+    mut commands: Commands,
+    strings: Strings,
+    theme: Res<Theme>,
+    accessibility: Res<AccessibilitySettings>,
+    mouse_input: Res<Input<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    villagers: Res<Villagers>,
+    mut query: Query<(Entity, &mut Villager, Option<&Children>)>,
+    bubbles: Query<Entity, With<SpeechBubble>>,
+) {
+    if !mouse_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor) = windows.get_single().ok().and_then(|w| w.cursor_position()) else {
+        return;
+    };
+    let Some(cursor) = cameras
+        .iter()
+        .find_map(|(camera, transform)| camera.viewport_to_world_2d(transform, cursor))
+    else {
+        return;
+    };
+
+    let size = WALK_FRAME_SIZE * VILLAGER_SCALE;
+
+    // Only the front-most villager under the cursor reacts, the same one drawn on top
+    let front = query
+        .iter()
+        .filter(|(_, villager, _)| Rect::from_center_size(villager.position, size).contains(cursor))
+        .max_by(|(_, a, _), (_, b, _)| {
+            villager_depth(a.position).total_cmp(&villager_depth(b.position))
+        })
+        .map(|(entity, ..)| entity);
+    let Some(Ok((entity, mut villager, children))) = front.map(|entity| query.get_mut(entity))
+    else {
+        return;
+    };
+
+    // Replace any bubble this villager is already showing
+    if let Some(children) = children {
+        for child in children.iter() {
+            if bubbles.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+    }
+
+    villager.state = VillagerState::Talking(Timer::from_seconds(2.5, TimerMode::Once));
+    let line = strings.get(greeting_key(&villagers.villagers[villager.index].0));
+
+    commands.entity(entity).with_children(|parent| {
+        parent
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: theme.button,
+                        // Grows with the text size setting, scale_text sizes the line itself
+                        custom_size: Some(
+                            Vec2::new(line.chars().count() as f32 * 7.0, 18.0)
+                                * accessibility.text_scale
+                                + Vec2::new(16.0, 4.0),
+                        ),
+                        ..default()
+                    },
+                    // Counter the villager scale so the bubble keeps its size
+                    transform: Transform::from_translation(Vec3::new(0.0, size.y / 2.0, 1.0))
+                        .with_scale(Vec3::splat(1.0 / VILLAGER_SCALE)),
+                    ..default()
+                },
+                SpeechBubble,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(
+                            line,
+                            TextStyle {
+                                font: theme.font.clone(),
+                                font_size: 18.0,
+                                color: theme.text,
+                            },
+                        )
+                        .with_alignment(TextAlignment::Center),
+                        transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.1)),
+                        ..default()
+                    },
+                    TextRole(ColorRole::Text),
+                ));
+            });
+    });
+}
+
+pub fn clear_speech_bubbles(
+    mut commands: Commands,
+    query: Query<(&Villager, &Children)>,
+    bubbles: Query<Entity, With<SpeechBubble>>,
+) {
+    for (villager, children) in query.iter() {
+        if let VillagerState::Talking(_) = villager.state {
+            continue;
+        }
+        for child in children.iter() {
+            if bubbles.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Walk sheet drawn from a villager's solo sprite, baker_solo.png walks with baker_walk.png
+pub fn walk_sheet_path(solo_path: &str) -> String {
+    solo_path.replace("_solo.png", "_walk.png")
+}
+
+fn random_ground_point(rng: &mut impl Rng) -> Vec2 {
+    Vec2::new(
+        rng.gen_range(GROUND_MIN.x..GROUND_MAX.x),
+        rng.gen_range(GROUND_MIN.y..GROUND_MAX.y),
+    )
+}
+
+// Villagers further down the screen are drawn in front
+fn villager_depth(position: Vec2) -> f32 {
+    2.0 - (position.y - GROUND_MIN.y) / (GROUND_MAX.y - GROUND_MIN.y)
+}
+
+fn greeting_key(char_file: &str) -> &'static str {
+    match char_file {
+        "characters/baker.png" => "greeting.baker",
+        "characters/bug_collector.png" => "greeting.bug_collector",
+        "characters/traveler.png" => "greeting.traveler",
+        "characters/farmer.png" => "greeting.farmer",
+        "characters/gardener.png" => "greeting.gardener",
+        "characters/librarian.png" => "greeting.librarian",
+        "characters/merchant.png" => "greeting.merchant",
+        "characters/penguin.png" => "greeting.penguin",
+        "characters/student.png" => "greeting.student",
+        "characters/cat.png" => "greeting.cat",
+        _ => "greeting.other",
+    }
+}